@@ -1,7 +1,7 @@
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::{JsCast, intern};
 use js_sys::Reflect;
-use web_sys::{HtmlElement, Element, Node, Window, History, Document, Text, Comment, DomTokenList, CssStyleSheet, CssStyleDeclaration, HtmlStyleElement, CssRule};
+use web_sys::{HtmlElement, Element, Node, Window, History, Document, Text, Comment, DomTokenList, CssStyleSheet, CssStyleDeclaration, HtmlStyleElement, CssRule, MediaQueryList, Storage, HtmlFormElement, FormData};
 use crate::utils::UnwrapJsExt;
 
 
@@ -15,7 +15,7 @@ pub(crate) fn set_property(obj: &JsValue, name: &str, value: &JsValue) {
 
 thread_local! {
     pub static WINDOW: Window = web_sys::window().unwrap_throw();
-    static DOCUMENT: Document = WINDOW.with(|w| w.document().unwrap_throw());
+    pub static DOCUMENT: Document = WINDOW.with(|w| w.document().unwrap_throw());
     static HISTORY: History = WINDOW.with(|w| w.history().unwrap_js());
 }
 
@@ -23,6 +23,43 @@ pub(crate) fn body() -> HtmlElement {
     DOCUMENT.with(|d| d.body().unwrap_throw())
 }
 
+/// Returns the `(name, value)` pairs of the form, files are skipped.
+pub(crate) fn form_entries(form: &HtmlFormElement) -> Vec<(String, String)> {
+    let data = FormData::new_with_form(form).unwrap_js();
+
+    data.entries()
+        .into_iter()
+        .filter_map(|entry| {
+            let entry: js_sys::Array = entry.unwrap_js().unchecked_into();
+            Some((entry.get(0).as_string()?, entry.get(1).as_string()?))
+        })
+        .collect()
+}
+
+/// Returns `None` if geolocation isn't supported.
+pub(crate) fn watch_position(success: &js_sys::Function, error: &js_sys::Function) -> Option<i32> {
+    WINDOW.with(|w| {
+        let geolocation = w.navigator().geolocation().ok()?;
+        geolocation.watch_position_with_error_callback(success, Some(error)).ok()
+    })
+}
+
+pub(crate) fn clear_watch(id: i32) {
+    WINDOW.with(|w| {
+        if let Ok(geolocation) = w.navigator().geolocation() {
+            geolocation.clear_watch(id);
+        }
+    })
+}
+
+pub(crate) fn is_online() -> bool {
+    WINDOW.with(|w| w.navigator().on_line())
+}
+
+pub(crate) fn is_visible() -> bool {
+    DOCUMENT.with(|d| !d.hidden())
+}
+
 pub(crate) fn ready_state() -> String {
     DOCUMENT.with(|d| d.ready_state())
 }
@@ -63,6 +100,18 @@ pub(crate) fn make_rule(sheet: &CssStyleSheet, rule: &str) -> Result<CssRule, Js
 }
 
 
+#[track_caller]
+pub(crate) fn local_storage() -> Option<Storage> {
+    // This returns an error if localStorage is disabled
+    WINDOW.with(|w| w.local_storage().ok().flatten())
+}
+
+#[track_caller]
+pub(crate) fn match_media(query: &str) -> MediaQueryList {
+    WINDOW.with(|w| w.match_media(query).unwrap_js().unwrap_throw())
+}
+
+
 pub(crate) fn get_element_by_id(id: &str) -> Element {
     DOCUMENT.with(|d| d.get_element_by_id(id).unwrap_throw())
 }
@@ -174,3 +223,50 @@ pub(crate) fn focus(elem: &HtmlElement) {
 pub(crate) fn blur(elem: &HtmlElement) {
     elem.blur().unwrap_js();
 }
+
+#[track_caller]
+pub(crate) fn set_timeout(callback: &js_sys::Function, ms: u32) -> i32 {
+    // TODO handle overflow ?
+    WINDOW.with(|w| w.set_timeout_with_callback_and_timeout_and_arguments_0(callback, ms as i32).unwrap_js())
+}
+
+pub(crate) fn clear_timeout(id: i32) {
+    WINDOW.with(|w| w.clear_timeout_with_handle(id))
+}
+
+#[track_caller]
+pub(crate) fn set_interval(callback: &js_sys::Function, ms: u32) -> i32 {
+    // TODO handle overflow ?
+    WINDOW.with(|w| w.set_interval_with_callback_and_timeout_and_arguments_0(callback, ms as i32).unwrap_js())
+}
+
+pub(crate) fn clear_interval(id: i32) {
+    WINDOW.with(|w| w.clear_interval_with_handle(id))
+}
+
+/// Returns `None` if `requestIdleCallback` isn't supported.
+pub(crate) fn request_idle_callback(callback: &js_sys::Function) -> Option<u32> {
+    WINDOW.with(|w| w.request_idle_callback(callback).ok())
+}
+
+pub(crate) fn cancel_idle_callback(id: u32) {
+    WINDOW.with(|w| w.cancel_idle_callback(id))
+}
+
+pub(crate) fn performance_now() -> f64 {
+    WINDOW.with(|w| w.performance().unwrap_throw().now())
+}
+
+pub(crate) fn console_error(message: &str) {
+    #[cfg(any(not(test), target_arch = "wasm32"))]
+    web_sys::console::error_1(&JsValue::from(message));
+
+    // The console isn't available when the tests are run natively
+    #[cfg(all(test, not(target_arch = "wasm32")))]
+    eprintln!("{}", message);
+}
+
+#[cfg(feature = "debug-poll")]
+pub(crate) fn console_log(message: &str) {
+    web_sys::console::log_1(&JsValue::from(message));
+}
@@ -2,19 +2,23 @@ use std::pin::Pin;
 use std::borrow::BorrowMut;
 use std::convert::AsRef;
 use std::future::Future;
+use std::rc::Rc;
+use std::collections::HashMap;
+use std::cell::Cell;
 use std::task::{Context, Poll};
 
 use once_cell::sync::Lazy;
-use futures_signals::signal::{Signal, not, channel, Receiver};
+use futures_signals::signal::{Signal, SignalExt, not, channel, Sender, Receiver};
 use futures_signals::signal_vec::SignalVec;
-use futures_util::FutureExt;
-use futures_channel::oneshot;
+use futures_util::{FutureExt, StreamExt};
+use futures_channel::{oneshot, mpsc};
 use discard::{Discard, DiscardOnDrop};
 use wasm_bindgen::{JsValue, UnwrapThrowExt, JsCast, intern};
-use web_sys::{HtmlElement, Node, EventTarget, Element, CssRule, CssStyleRule, CssStyleSheet, CssStyleDeclaration, ShadowRoot, ShadowRootMode, ShadowRootInit, Text};
+use wasm_bindgen::closure::Closure;
+use web_sys::{HtmlElement, HtmlFormElement, Position, PositionError, Node, EventTarget, Element, CssRule, CssStyleRule, CssStyleSheet, CssStyleDeclaration, ShadowRoot, ShadowRootMode, ShadowRootInit, Text};
 
 use crate::bindings;
-use crate::bindings::WINDOW;
+use crate::bindings::{WINDOW, DOCUMENT};
 use crate::callbacks::Callbacks;
 use crate::traits::*;
 use crate::fragment::{Fragment, FragmentBuilder};
@@ -244,13 +248,27 @@ impl WindowSize {
 }
 
 #[derive(Debug)]
-struct WindowSizeSignal {
-    _listener: EventListener,
-    receiver: Receiver<WindowSize>,
+struct EventSignal<A> {
+    _listeners: Vec<DiscardOnDrop<EventListener>>,
+    receiver: Receiver<A>,
 }
 
-impl Signal for WindowSizeSignal {
-    type Item = WindowSize;
+impl<A> EventSignal<A> {
+    #[inline]
+    fn new(listener: EventListener, receiver: Receiver<A>) -> Self {
+        Self::new_many(vec![listener], receiver)
+    }
+
+    fn new_many(listeners: Vec<EventListener>, receiver: Receiver<A>) -> Self {
+        Self {
+            _listeners: listeners.into_iter().map(DiscardOnDrop::new).collect(),
+            receiver,
+        }
+    }
+}
+
+impl<A> Signal for EventSignal<A> {
+    type Item = A;
 
     fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
         Pin::new(&mut self.receiver).poll_change(cx)
@@ -261,25 +279,537 @@ impl Signal for WindowSizeSignal {
 /// `Signal` which gives the current width / height of the window.
 ///
 /// When the window is resized, it will automatically update with the new size.
+/// It only updates if the size is different from the previous size.
+///
+/// The `resize` listener is removed when the `Signal` is dropped.
 pub fn window_size() -> impl Signal<Item = WindowSize> {
-    let (sender, receiver) = channel(WindowSize::new());
+    let mut size = WindowSize::new();
+
+    let (sender, receiver) = channel(size);
 
     let listener = WINDOW.with(|window| {
         on(window, &EventOptions::default(), move |_: crate::events::Resize| {
+            let new_size = WindowSize::new();
+
+            if new_size != size {
+                size = new_size;
+
+                crate::__unwrap!(
+                    sender.send(new_size),
+                    _e => panic!("Invalid window_size() state"),
+                )
+            }
+        })
+    });
+
+    EventSignal::new(listener, receiver)
+}
+
+
+/// `Signal` which says whether the [CSS media query](https://developer.mozilla.org/en-US/docs/Web/CSS/Media_Queries/Using_media_queries) matches or not.
+///
+/// When the media query changes, it will automatically update.
+///
+/// ```rust
+/// media_query("(prefers-color-scheme: dark)")
+/// ```
+///
+/// The `change` listener is removed when the `Signal` is dropped.
+#[track_caller]
+pub fn media_query(query: &str) -> impl Signal<Item = bool> {
+    let list = bindings::match_media(query);
+
+    let mut matches = list.matches();
+
+    let (sender, receiver) = channel(matches);
+
+    let listener = EventListener::new(&list.clone(), "change", &EventOptions::default(), move |_| {
+        let new_matches = list.matches();
+
+        if new_matches != matches {
+            matches = new_matches;
+
             crate::__unwrap!(
-                sender.send(WindowSize::new()),
-                _e => panic!("Invalid window_size() state"),
+                sender.send(new_matches),
+                _e => panic!("Invalid media_query() state"),
             )
+        }
+    });
+
+    EventSignal::new(listener, receiver)
+}
+
+
+/// `Signal` which says whether the document is visible or not.
+///
+/// It is `false` when the document is hidden, for example when the browser tab is
+/// in the background. This is useful for pausing expensive work while the user
+/// can't see it.
+///
+/// The `visibilitychange` listener is removed when the `Signal` is dropped.
+pub fn visibility() -> impl Signal<Item = bool> {
+    let mut visible = bindings::is_visible();
+
+    let (sender, receiver) = channel(visible);
+
+    let listener = DOCUMENT.with(|document| {
+        EventListener::new(document, "visibilitychange", &EventOptions::default(), move |_| {
+            let new_visible = bindings::is_visible();
+
+            if new_visible != visible {
+                visible = new_visible;
+
+                crate::__unwrap!(
+                    sender.send(new_visible),
+                    _e => panic!("Invalid visibility() state"),
+                )
+            }
         })
     });
 
-    WindowSizeSignal {
-        _listener: listener,
+    EventSignal::new(listener, receiver)
+}
+
+
+/// `Signal` which says whether the browser is online or not.
+///
+/// It uses `navigator.onLine`, and it automatically updates when the `online` or
+/// `offline` events happen. It only updates if the status is different from the
+/// previous status.
+///
+/// Both listeners are removed when the `Signal` is dropped.
+pub fn online() -> impl Signal<Item = bool> {
+    let is_online = Rc::new(Cell::new(bindings::is_online()));
+
+    let (sender, receiver) = channel(is_online.get());
+
+    fn update(is_online: &Cell<bool>, sender: &Sender<bool>, new_online: bool) {
+        if new_online != is_online.get() {
+            is_online.set(new_online);
+
+            crate::__unwrap!(
+                sender.send(new_online),
+                _e => panic!("Invalid online() state"),
+            )
+        }
+    }
+
+    let listeners = WINDOW.with(|window| {
+        vec![
+            on(window, &EventOptions::default(), clone!(is_online, sender => move |_: crate::events::Online| {
+                update(&is_online, &sender, true);
+            })),
+            on(window, &EventOptions::default(), move |_: crate::events::Offline| {
+                update(&is_online, &sender, false);
+            }),
+        ]
+    });
+
+    EventSignal::new_many(listeners, receiver)
+}
+
+
+/// `Signal` which gives the `(scrollLeft, scrollTop)` position of the element.
+///
+/// When the element is scrolled, it will automatically update with the new position.
+/// It updates at most once per animation frame, and only if the position is different
+/// from the previous position. The initial position is outputted immediately.
+///
+/// The `scroll` listener is removed when the `Signal` is dropped.
+pub fn scroll_position(element: &Element) -> impl Signal<Item = (f64, f64)> {
+    fn get_position(element: &Element) -> (f64, f64) {
+        (element.scroll_left() as f64, element.scroll_top() as f64)
+    }
+
+    let (sender, receiver) = channel(get_position(element));
+
+    let listener = on(element, &EventOptions::default(), clone!(element => move |_: crate::events::Scroll| {
+        crate::__unwrap!(
+            sender.send(get_position(&element)),
+            _e => panic!("Invalid scroll_position() state"),
+        )
+    }));
+
+    // This dedupes after throttling, so that a position which changes and then changes back
+    // within the same frame isn't outputted again
+    EventSignal::new(listener, receiver).throttle_frames().dedupe()
+}
+
+
+/// Unlike [`EventSignal`], this outputs every event.
+///
+/// Because there might not be an event yet, it outputs `None` first (if there isn't an
+/// event already), and after that every event is outputted as `Some`. This is the same
+/// as [`from_stream`](futures_signals::signal::from_stream).
+#[derive(Debug)]
+struct EventStream<A> {
+    _listeners: Vec<DiscardOnDrop<EventListener>>,
+    receiver: mpsc::UnboundedReceiver<A>,
+    first: bool,
+}
+
+impl<A> EventStream<A> {
+    #[inline]
+    fn new(listener: EventListener, receiver: mpsc::UnboundedReceiver<A>) -> Self {
+        Self::new_many(vec![listener], receiver)
+    }
+
+    fn new_many(listeners: Vec<EventListener>, receiver: mpsc::UnboundedReceiver<A>) -> Self {
+        Self {
+            _listeners: listeners.into_iter().map(DiscardOnDrop::new).collect(),
+            receiver,
+            first: true,
+        }
+    }
+}
+
+impl<A> Signal for EventStream<A> {
+    type Item = Option<A>;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        match self.receiver.poll_next_unpin(cx) {
+            Poll::Ready(Some(value)) => {
+                self.first = false;
+                Poll::Ready(Some(Some(value)))
+            },
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => if self.first {
+                self.first = false;
+                Poll::Ready(Some(None))
+
+            } else {
+                Poll::Pending
+            },
+        }
+    }
+}
+
+
+/// `Signal` which outputs the [`KeyDown`](crate::events::KeyDown) events for the keys in `keys`.
+///
+/// The initial value is `None`, after that every matching event is outputted as `Some`.
+///
+/// The keys are compared with [`KeyboardEvent.key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key):
+///
+/// ```rust
+/// key_down_events(&body(), &["Escape", "Enter"])
+/// ```
+///
+/// The `keydown` listener is removed when the `Signal` is dropped.
+pub fn key_down_events(target: &EventTarget, keys: &[&str]) -> impl Signal<Item = Option<crate::events::KeyDown>> {
+    let keys: Vec<String> = keys.iter().map(|key| key.to_string()).collect();
+
+    let (sender, receiver) = mpsc::unbounded();
+
+    let listener = on(target, &EventOptions::default(), move |e: crate::events::KeyDown| {
+        let key = e.key();
+
+        if keys.contains(&key) {
+            crate::__unwrap!(
+                sender.unbounded_send(e),
+                _e => panic!("Invalid key_down_events() state"),
+            )
+        }
+    });
+
+    EventStream::new(listener, receiver)
+}
+
+/// `Signal` which outputs the [key name](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key) when one of the keys in `keys` is pressed.
+///
+/// The initial value is `None`. See [`key_down_events`] for more details.
+pub fn key_down(target: &EventTarget, keys: &[&str]) -> impl Signal<Item = Option<String>> {
+    key_down_events(target, keys).map(|e| e.map(|e| e.key()))
+}
+
+
+/// `Signal` which outputs the `(x, y)` movement of the pointer while it is being dragged.
+///
+/// Dragging starts with `pointerdown` on `target` and stops with `pointerup` or
+/// `pointercancel`. While dragging, every `pointermove` outputs the distance that the
+/// pointer moved since the previous `pointermove` (or since the `pointerdown`).
+///
+/// The `pointermove`, `pointerup`, and `pointercancel` listeners are on the window,
+/// so dragging continues even if the pointer leaves `target`. Only the pointer which
+/// started the drag is tracked, other pointers are ignored until the drag stops.
+///
+/// The initial value is `None`, after that every movement is outputted as `Some`.
+/// Nothing is outputted while not dragging.
+///
+/// All four listeners are removed when the `Signal` is dropped.
+pub fn drag_deltas(target: &EventTarget) -> impl Signal<Item = Option<(f64, f64)>> {
+    // The pointer id and the previous position
+    let drag: Rc<Cell<Option<(i32, f64, f64)>>> = Rc::new(Cell::new(None));
+
+    let (sender, receiver) = mpsc::unbounded();
+
+    fn stop(drag: &Cell<Option<(i32, f64, f64)>>, pointer_id: i32) {
+        if let Some((id, _, _)) = drag.get() {
+            if id == pointer_id {
+                drag.set(None);
+            }
+        }
+    }
+
+    let mut listeners = vec![
+        on(target, &EventOptions::default(), clone!(drag => move |e: crate::events::PointerDown| {
+            if drag.get().is_none() {
+                drag.set(Some((e.pointer_id(), e.x() as f64, e.y() as f64)));
+            }
+        })),
+    ];
+
+    WINDOW.with(|window| {
+        listeners.push(on(window, &EventOptions::default(), clone!(drag => move |e: crate::events::PointerMove| {
+            if let Some((id, old_x, old_y)) = drag.get() {
+                if id == e.pointer_id() {
+                    let (x, y) = (e.x() as f64, e.y() as f64);
+
+                    drag.set(Some((id, x, y)));
+
+                    crate::__unwrap!(
+                        sender.unbounded_send((x - old_x, y - old_y)),
+                        _e => panic!("Invalid drag_deltas() state"),
+                    )
+                }
+            }
+        })));
+
+        listeners.push(on(window, &EventOptions::default(), clone!(drag => move |e: crate::events::PointerUp| {
+            stop(&drag, e.pointer_id());
+        })));
+
+        listeners.push(on(window, &EventOptions::default(), move |e: crate::events::PointerCancel| {
+            stop(&drag, e.pointer_id());
+        }));
+    });
+
+    EventStream::new_many(listeners, receiver)
+}
+
+/// `Signal` which outputs the data of the form whenever it is submitted.
+///
+/// The data is a `HashMap` of the field names to their values. Fields which contain
+/// files are skipped.
+///
+/// It calls `preventDefault` on the `submit` event, so the page isn't reloaded.
+///
+/// The initial value is the current data of the form, after that it only updates when
+/// the form is submitted.
+///
+/// The `submit` listener is removed when the `Signal` is dropped.
+pub fn form_data(form: &HtmlFormElement) -> impl Signal<Item = HashMap<String, String>> {
+    fn get_data(form: &HtmlFormElement) -> HashMap<String, String> {
+        bindings::form_entries(form).into_iter().collect()
+    }
+
+    let (sender, receiver) = channel(get_data(form));
+
+    let listener = on(form, &EventOptions::default(), clone!(form => move |e: crate::events::Submit| {
+        e.prevent_default();
+
+        crate::__unwrap!(
+            sender.send(get_data(&form)),
+            _e => panic!("Invalid form_data() state"),
+        )
+    }));
+
+    EventSignal::new(listener, receiver)
+}
+
+
+type GeolocationResult = Result<(f64, f64), String>;
+
+struct GeolocationSignal {
+    watch_id: Option<i32>,
+    _success: Closure<dyn FnMut(Position)>,
+    _error: Closure<dyn FnMut(PositionError)>,
+    receiver: mpsc::UnboundedReceiver<GeolocationResult>,
+    first: bool,
+}
+
+impl Drop for GeolocationSignal {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(watch_id) = self.watch_id {
+            bindings::clear_watch(watch_id);
+        }
+    }
+}
+
+impl std::fmt::Debug for GeolocationSignal {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("GeolocationSignal")
+            .field("watch_id", &self.watch_id)
+            .finish()
+    }
+}
+
+impl Signal for GeolocationSignal {
+    type Item = Option<GeolocationResult>;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        match self.receiver.poll_next_unpin(cx) {
+            Poll::Ready(Some(value)) => {
+                self.first = false;
+                Poll::Ready(Some(Some(value)))
+            },
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => if self.first {
+                self.first = false;
+                Poll::Ready(Some(None))
+
+            // If geolocation isn't supported then the error has already been outputted
+            } else if self.watch_id.is_none() {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            },
+        }
+    }
+}
+
+/// `Signal` which gives the `(latitude, longitude)` of the user.
+///
+/// It uses [`navigator.geolocation.watchPosition`](https://developer.mozilla.org/en-US/docs/Web/API/Geolocation/watchPosition),
+/// so it updates whenever the position changes. If the position cannot be retrieved
+/// (for example because the user denied permission) then it outputs an `Err` with the
+/// error message.
+///
+/// The initial value is `None` (unless the result is already available), after that
+/// it outputs `Some` as soon as the position is available.
+///
+/// If geolocation isn't supported then it outputs an `Err` and then the `Signal` ends.
+///
+/// The watch is cleared when the `Signal` is dropped.
+pub fn geolocation() -> impl Signal<Item = Option<Result<(f64, f64), String>>> {
+    let (sender, receiver) = mpsc::unbounded();
+
+    let success = Closure::wrap(Box::new(clone!(sender => move |position: Position| {
+        let coords = position.coords();
+
+        crate::__unwrap!(
+            sender.unbounded_send(Ok((coords.latitude(), coords.longitude()))),
+            _e => panic!("Invalid geolocation() state"),
+        )
+    })) as Box<dyn FnMut(Position)>);
+
+    let error = Closure::wrap(Box::new(clone!(sender => move |error: PositionError| {
+        crate::__unwrap!(
+            sender.unbounded_send(Err(error.message())),
+            _e => panic!("Invalid geolocation() state"),
+        )
+    })) as Box<dyn FnMut(PositionError)>);
+
+    let watch_id = bindings::watch_position(success.as_ref().unchecked_ref(), error.as_ref().unchecked_ref());
+
+    if watch_id.is_none() {
+        crate::__unwrap!(
+            sender.unbounded_send(Err("Geolocation is not supported".to_string())),
+            _e => panic!("Invalid geolocation() state"),
+        )
+    }
+
+    GeolocationSignal {
+        watch_id,
+        _success: success,
+        _error: error,
         receiver,
+        first: true,
     }
 }
 
 
+/// This is returned by the [`local_storage`] function.
+///
+/// It is used to change the value in [`localStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/localStorage).
+///
+/// If `localStorage` is unavailable then the changes are not persisted,
+/// but the `Signal` is still updated.
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    key: String,
+    sender: Sender<Option<String>>,
+}
+
+impl LocalStorage {
+    fn get_item(key: &str) -> Option<String> {
+        bindings::local_storage()?.get_item(key).ok().flatten()
+    }
+
+    #[inline]
+    pub fn get(&self) -> Option<String> {
+        Self::get_item(&self.key)
+    }
+
+    #[track_caller]
+    pub fn set(&self, value: &str) {
+        if let Some(storage) = bindings::local_storage() {
+            storage.set_item(&self.key, value).unwrap_js();
+        }
+
+        // This is an error if the Signal was dropped, in which case there is nothing to update
+        let _ = self.sender.send(Some(value.to_owned()));
+    }
+
+    #[track_caller]
+    pub fn remove(&self) {
+        if let Some(storage) = bindings::local_storage() {
+            storage.remove_item(&self.key).unwrap_js();
+        }
+
+        let _ = self.sender.send(None);
+    }
+}
+
+/// `Signal` which gives the value of `key` in [`localStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/localStorage).
+///
+/// It returns a [`LocalStorage`] which is used to change the value, and a `Signal`
+/// which contains `None` if the key doesn't exist (or if `localStorage` is unavailable).
+///
+/// The `Signal` will automatically update when the value is changed by [`LocalStorage`]
+/// or by a different browser tab.
+///
+/// The `storage` listener is removed when the `Signal` is dropped.
+pub fn local_storage(key: &str) -> (LocalStorage, impl Signal<Item = Option<String>>) {
+    let (sender, receiver) = channel(LocalStorage::get_item(key));
+
+    let listener = WINDOW.with(|window| {
+        let key = key.to_owned();
+        let sender = sender.clone();
+
+        EventListener::new(window, "storage", &EventOptions::default(), move |e| {
+            let e: &web_sys::StorageEvent = e.unchecked_ref();
+
+            let is_local = match (e.storage_area(), bindings::local_storage()) {
+                (Some(area), Some(storage)) => JsValue::from(area) == JsValue::from(storage),
+                _ => false,
+            };
+
+            if is_local {
+                match e.key() {
+                    // localStorage.clear() was called
+                    None => {
+                        let _ = sender.send(None);
+                    },
+                    Some(changed) => if changed == key {
+                        let _ = sender.send(e.new_value());
+                    },
+                }
+            }
+        })
+    });
+
+    let writer = LocalStorage {
+        key: key.to_owned(),
+        sender,
+    };
+
+    (writer, EventSignal::new(listener, receiver))
+}
+
+
 // TODO should this intern ?
 #[inline]
 pub fn text(value: &str) -> Dom {
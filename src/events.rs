@@ -426,6 +426,12 @@ static_event_impl!(Resize => "resize");
 make_event!(SelectionChange => web_sys::Event);
 static_event_impl!(SelectionChange => "selectionchange");
 
+make_event!(Online => web_sys::Event);
+static_event_impl!(Online => "online");
+
+make_event!(Offline => web_sys::Event);
+static_event_impl!(Offline => "offline");
+
 
 
 impl Input {
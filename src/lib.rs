@@ -12,6 +12,7 @@ mod macros;
 mod utils;
 mod bindings;
 mod callbacks;
+mod timer;
 mod operations;
 mod dom;
 mod fragment;
@@ -22,4 +23,5 @@ pub use fragment::*;
 pub mod traits;
 pub mod animation;
 pub mod routing;
+pub mod signal;
 pub mod events;
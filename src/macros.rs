@@ -771,3 +771,73 @@ macro_rules! __internal_styles {
         })
     };
 }
+
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __internal_zip_into_signal {
+    ($signal:expr) => {
+        $signal
+    };
+    ($signal:expr, $($rest:expr),+) => {
+        $crate::signal::SignalExtra::map2(
+            $signal,
+            $crate::__internal_zip_into_signal!($($rest),+),
+            |left, right| (::std::clone::Clone::clone(left), ::std::clone::Clone::clone(right)),
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __internal_zip_into_pattern {
+    ($field:ident) => {
+        $field
+    };
+    ($field:ident, $($rest:ident),+) => {
+        ($field, $crate::__internal_zip_into_pattern!($($rest),+))
+    };
+}
+
+/// Combines multiple signals into a single signal of a struct.
+///
+/// Instead of writing this...
+///
+/// ```rust
+/// name.signal_cloned().map2(age.signal(), |name, age| {
+///     FormState {
+///         name: name.clone(),
+///         age: age.clone(),
+///     }
+/// })
+/// ```
+///
+/// ...you can instead write this:
+///
+/// ```rust
+/// zip_into!(FormState {
+///     name: name.signal_cloned(),
+///     age: age.signal(),
+/// })
+/// ```
+///
+/// Whenever any of the signals change, it will create a new struct with the latest
+/// values of all of the signals. The values must implement [`Clone`].
+///
+/// It uses [`map2`](crate::signal::SignalExtra::map2), so nothing is outputted until
+/// all of the signals have a value. It needs at least two signals.
+#[macro_export]
+macro_rules! zip_into {
+    ($($name:ident)::+ { $field:ident: $signal:expr, $($rest_field:ident: $rest_signal:expr),+ $(,)? }) => {
+        $crate::signal::SignalExtra::map2(
+            $signal,
+            $crate::__internal_zip_into_signal!($($rest_signal),+),
+            |$field, $crate::__internal_zip_into_pattern!($($rest_field),+)| {
+                $($name)::+ {
+                    $field: ::std::clone::Clone::clone($field),
+                    $($rest_field: ::std::clone::Clone::clone($rest_field),)+
+                }
+            },
+        )
+    };
+}
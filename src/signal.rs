@@ -0,0 +1,5271 @@
+use std::fmt;
+use std::hash::Hash;
+use std::collections::{HashMap, VecDeque};
+use std::ops::{Deref, DerefMut};
+use std::rc::{Rc, Weak};
+use std::cell::{RefCell, Ref, RefMut};
+use std::pin::Pin;
+use std::future::Future;
+use std::task::{Poll, Waker, Context};
+
+use discard::DiscardOnDrop;
+use futures_channel::oneshot;
+use futures_util::future::{ready, FutureExt};
+use futures_util::stream::{StreamExt, TryStreamExt};
+use futures_signals::CancelableFutureHandle;
+use futures_signals::signal::{Signal, SignalExt, Mutable, Map, Flatten};
+use pin_project::pin_project;
+use wasm_bindgen::UnwrapThrowExt;
+
+use crate::bindings;
+use crate::animation;
+use crate::animation::Timestamps;
+use crate::operations::spawn_future;
+use crate::timer::{Ticker, Delay, Idle};
+
+
+#[cfg(feature = "debug-poll")]
+mod trace {
+    use std::cell::Cell;
+    use std::task::Poll;
+
+    use crate::bindings;
+
+
+    thread_local! {
+        static DEPTH: Cell<usize> = const { Cell::new(0) };
+    }
+
+    pub(super) struct PollTrace {
+        name: &'static str,
+        depth: usize,
+    }
+
+    impl PollTrace {
+        pub(super) fn enter(name: &'static str) -> Self {
+            let depth = DEPTH.with(|depth| {
+                let old = depth.get();
+                depth.set(old + 1);
+                old
+            });
+
+            bindings::console_log(&format!("{:indent$}{}", "", name, indent = depth * 2));
+
+            Self { name, depth }
+        }
+
+        pub(super) fn exit<A>(&self, result: &Poll<Option<A>>) {
+            let result = match result {
+                Poll::Ready(Some(_)) => "Poll::Ready(Some(..))",
+                Poll::Ready(None) => "Poll::Ready(None)",
+                Poll::Pending => "Poll::Pending",
+            };
+
+            bindings::console_log(&format!("{:indent$}{} => {}", "", self.name, result, indent = self.depth * 2));
+        }
+    }
+
+    impl Drop for PollTrace {
+        #[inline]
+        fn drop(&mut self) {
+            DEPTH.with(|depth| depth.set(self.depth));
+        }
+    }
+}
+
+// Logs the combinator name and the result of the poll, with indentation for nested polls
+#[cfg(feature = "debug-poll")]
+macro_rules! trace_poll {
+    ($name:expr, $body:expr) => {{
+        let trace = self::trace::PollTrace::enter($name);
+        #[allow(clippy::redundant_closure_call)]
+        let result = (move || $body)();
+        trace.exit(&result);
+        result
+    }};
+}
+
+// This is zero-cost when the `debug-poll` feature is disabled
+#[cfg(not(feature = "debug-poll"))]
+macro_rules! trace_poll {
+    ($name:expr, $body:expr) => {
+        $body
+    };
+}
+
+
+// Signals must output a value the first time they are polled, so if the first poll is
+// `Pending` this outputs `None` instead, and every other value is wrapped in `Some`.
+// This is the same as `from_stream` in futures-signals.
+fn poll_first<A>(first: &mut bool, poll: Poll<Option<A>>) -> Poll<Option<Option<A>>> {
+    match poll {
+        Poll::Ready(value) => {
+            *first = false;
+            Poll::Ready(value.map(Some))
+        },
+        Poll::Pending => if *first {
+            *first = false;
+            Poll::Ready(Some(None))
+
+        } else {
+            Poll::Pending
+        },
+    }
+}
+
+
+#[derive(Debug)]
+struct SignalCellState {
+    changed: bool,
+    done: bool,
+    waker: Option<Waker>,
+}
+
+impl SignalCellState {
+    fn new() -> Self {
+        Self {
+            changed: true,
+            done: false,
+            waker: None,
+        }
+    }
+}
+
+
+struct SignalCellInner<A> {
+    value: Rc<RefCell<A>>,
+    // TODO make this more efficient
+    signals: RefCell<Vec<Weak<RefCell<SignalCellState>>>>,
+}
+
+impl<A> SignalCellInner<A> {
+    fn notify(&self, done: bool) {
+        self.signals.borrow_mut().retain(|state| {
+            if let Some(state) = state.upgrade() {
+                let waker = {
+                    let mut lock = state.borrow_mut();
+
+                    if done {
+                        lock.done = true;
+
+                    } else {
+                        lock.changed = true;
+                    }
+
+                    lock.waker.take()
+                };
+
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+
+                true
+
+            } else {
+                false
+            }
+        });
+    }
+}
+
+impl<A> Drop for SignalCellInner<A> {
+    #[inline]
+    fn drop(&mut self) {
+        self.notify(true);
+    }
+}
+
+
+/// Wraps an existing `Rc<RefCell<A>>` so that it can be used as a `Signal`.
+///
+/// This is similar to `Mutable`, except it works with pre-existing shared state.
+///
+/// Any changes made with [`borrow_mut`](SignalCell::borrow_mut) will automatically
+/// update the signals. If the `Rc<RefCell<A>>` is mutated directly then you must
+/// call [`notify`](SignalCell::notify) afterwards.
+///
+/// When every `SignalCell` is dropped, the signals will end.
+pub struct SignalCell<A> {
+    inner: Rc<SignalCellInner<A>>,
+}
+
+impl<A> SignalCell<A> {
+    #[inline]
+    pub fn new(value: A) -> Self {
+        Self::from_cell(Rc::new(RefCell::new(value)))
+    }
+
+    #[inline]
+    pub fn from_cell(cell: Rc<RefCell<A>>) -> Self {
+        Self {
+            inner: Rc::new(SignalCellInner {
+                value: cell,
+                signals: RefCell::new(vec![]),
+            }),
+        }
+    }
+
+    /// Returns the underlying `Rc<RefCell<A>>`.
+    #[inline]
+    pub fn cell(&self) -> &Rc<RefCell<A>> {
+        &self.inner.value
+    }
+
+    #[inline]
+    #[track_caller]
+    pub fn borrow(&self) -> Ref<'_, A> {
+        self.inner.value.borrow()
+    }
+
+    /// Mutably borrows the value, the signals are updated when the borrow is dropped.
+    ///
+    /// The signals are only updated if the value was mutably dereferenced.
+    #[inline]
+    #[track_caller]
+    pub fn borrow_mut(&self) -> SignalCellRefMut<'_, A> {
+        SignalCellRefMut {
+            inner: &self.inner,
+            lock: Some(self.inner.value.borrow_mut()),
+            mutated: false,
+        }
+    }
+
+    /// Updates the signals, this must be called after mutating the `Rc<RefCell<A>>` directly.
+    #[inline]
+    pub fn notify(&self) {
+        self.inner.notify(false);
+    }
+
+    pub fn signal(&self) -> SignalCellSignal<A> where A: Clone {
+        let state = Rc::new(RefCell::new(SignalCellState::new()));
+
+        self.inner.signals.borrow_mut().push(Rc::downgrade(&state));
+
+        SignalCellSignal {
+            value: self.inner.value.clone(),
+            state,
+        }
+    }
+}
+
+impl<A> Clone for SignalCell<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<A> fmt::Debug for SignalCell<A> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner.value.try_borrow() {
+            Ok(value) => fmt.debug_tuple("SignalCell")
+                .field(&*value)
+                .finish(),
+            Err(_) => fmt.debug_tuple("SignalCell")
+                .field(&"<borrowed>")
+                .finish(),
+        }
+    }
+}
+
+/// Wraps an existing `Rc<RefCell<A>>` so that it can be used as a `Signal`.
+///
+/// See [`SignalCell`] for more details.
+#[inline]
+pub fn from_cell<A>(cell: Rc<RefCell<A>>) -> SignalCell<A> {
+    SignalCell::from_cell(cell)
+}
+
+
+/// This is returned by the [`SignalCell::borrow_mut`] method.
+pub struct SignalCellRefMut<'a, A> {
+    inner: &'a SignalCellInner<A>,
+    // This is always `Some` until it is dropped
+    lock: Option<RefMut<'a, A>>,
+    mutated: bool,
+}
+
+impl<'a, A> Deref for SignalCellRefMut<'a, A> {
+    type Target = A;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.lock.as_ref().unwrap_throw()
+    }
+}
+
+impl<'a, A> DerefMut for SignalCellRefMut<'a, A> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.mutated = true;
+        self.lock.as_mut().unwrap_throw()
+    }
+}
+
+impl<'a, A> Drop for SignalCellRefMut<'a, A> {
+    #[inline]
+    fn drop(&mut self) {
+        // The borrow is released first so that the signals can borrow the value
+        self.lock = None;
+
+        if self.mutated {
+            self.inner.notify(false);
+        }
+    }
+}
+
+impl<'a, A> fmt::Debug for SignalCellRefMut<'a, A> where A: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("SignalCellRefMut")
+            .field(&**self)
+            .finish()
+    }
+}
+
+
+/// This is returned by the [`SignalCell::signal`] method.
+#[must_use = "Signals do nothing unless polled"]
+#[derive(Debug)]
+pub struct SignalCellSignal<A> {
+    value: Rc<RefCell<A>>,
+    state: Rc<RefCell<SignalCellState>>,
+}
+
+impl<A> Unpin for SignalCellSignal<A> {}
+
+impl<A> Signal for SignalCellSignal<A> where A: Clone {
+    type Item = A;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("SignalCellSignal", {
+            let mut lock = self.state.borrow_mut();
+
+            if lock.changed {
+                lock.changed = false;
+                Poll::Ready(Some(self.value.borrow().clone()))
+
+            } else if lock.done {
+                Poll::Ready(None)
+
+            } else {
+                lock.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+    }
+}
+
+
+pub trait SignalExtra: Signal {
+    /// Maps each element of a `Vec`, reusing the previous results for keys which haven't changed.
+    ///
+    /// Whenever the signal changes, `key` is called on every element. If the key existed
+    /// in the previous `Vec` then the cached result is cloned, otherwise `transform` is
+    /// called to create a new result. Results for keys which no longer exist are dropped.
+    ///
+    /// This is useful for rendering keyed lists from a signal of a whole `Vec`.
+    #[inline]
+    fn map_index<A, B, K, F, T>(self, key: F, transform: T) -> MapIndex<Self, K, B, F, T>
+        where K: Eq + Hash,
+              B: Clone,
+              F: FnMut(&A) -> K,
+              T: FnMut(A) -> B,
+              Self: Signal<Item = Vec<A>> + Sized {
+        MapIndex {
+            signal: self,
+            cache: HashMap::new(),
+            key,
+            transform,
+        }
+    }
+
+    /// Every `tick_ms` milliseconds it outputs how many milliseconds have passed since this signal last changed.
+    ///
+    /// Whenever this signal changes it will output `0.0`.
+    ///
+    /// The initial value is always `0.0`, even if this signal hasn't changed yet, in which
+    /// case the time is counted from the first poll. If this signal ends then it will keep
+    /// outputting the time since the last change.
+    ///
+    /// This is useful for displaying things like "last updated 12 seconds ago".
+    #[inline]
+    fn elapsed(self, tick_ms: u32) -> Elapsed<Self> where Self: Sized {
+        Elapsed {
+            signal: Some(self),
+            ticker: Ticker::new(tick_ms),
+            last_change: None,
+        }
+    }
+
+    /// Calls `callback` for each value of the signal, stopping when `callback` returns an error.
+    ///
+    /// The error is logged with `console.error`. The subscription also stops if the
+    /// returned [`DropHandle`] is dropped or [`stopped`](DropHandle::stop).
+    fn for_each_try<E, F>(self, mut callback: F) -> DropHandle
+        where E: fmt::Debug + 'static,
+              F: FnMut(Self::Item) -> Result<(), E> + 'static,
+              Self: Sized + 'static {
+
+        let future = self.to_stream()
+            .map(Ok)
+            .try_for_each(move |value| ready(callback(value)));
+
+        DropHandle {
+            _handle: spawn_future(async move {
+                if let Err(e) = future.await {
+                    bindings::console_error(&format!("{:?}", e));
+                }
+            }),
+        }
+    }
+
+    /// Smoothly animates from the previous value to the new value.
+    ///
+    /// Whenever this signal changes, it will output an intermediate value on every
+    /// animation frame, until it reaches the new value after `duration_ms` milliseconds.
+    ///
+    /// If this signal changes while it is animating, then it will start animating
+    /// from the current intermediate value.
+    ///
+    /// The first value is outputted immediately without animating.
+    #[inline]
+    fn tween(self, duration_ms: u32) -> Tween<Self> where Self: Signal<Item = f64> + Sized {
+        Tween {
+            signal: Some(self),
+            duration: duration_ms as f64,
+            timestamps: None,
+            current: None,
+            start: 0.0,
+            end: 0.0,
+            start_time: None,
+        }
+    }
+
+    /// Converts a signal of `Result<A, E>` into a signal of `A`.
+    ///
+    /// `Ok` values are outputted unchanged, `Err` values are passed to `callback` which
+    /// returns a fallback value.
+    #[inline]
+    fn unwrap_or_else<A, E, F>(self, callback: F) -> UnwrapOrElse<Self, F>
+        where F: FnMut(E) -> A,
+              Self: Signal<Item = Result<A, E>> + Sized {
+        UnwrapOrElse {
+            signal: self,
+            callback,
+        }
+    }
+
+    /// Outputs the values of whichever signal changes first.
+    ///
+    /// Both signals are polled until one of them changes, after that the other
+    /// signal is dropped and only the winning signal is used.
+    ///
+    /// If both signals change at the same time then `self` wins.
+    ///
+    /// If a signal ends before it changes then the other signal wins.
+    #[inline]
+    fn race<B>(self, other: B) -> Race<Self, B>
+        where B: Signal<Item = Self::Item>,
+              Self: Sized {
+        Race {
+            left: Some(self),
+            right: Some(other),
+        }
+    }
+
+    /// Waits until this signal stops changing for `ms` milliseconds.
+    ///
+    /// If `leading` is `true` then it outputs the first value immediately, and then
+    /// ignores the following changes until `ms` milliseconds have passed without
+    /// any changes.
+    ///
+    /// If `trailing` is `true` then it outputs the latest value after `ms`
+    /// milliseconds have passed without any changes.
+    ///
+    /// If both are `true` then a value which was outputted on the leading edge is
+    /// never outputted again on the trailing edge, so a single change is only
+    /// outputted once.
+    ///
+    /// If both are `false` then it behaves as if `trailing` is `true`.
+    ///
+    /// When this signal ends, it will wait for the timer before ending.
+    #[inline]
+    fn debounce_config(self, ms: u32, leading: bool, trailing: bool) -> Debounce<Self> where Self: Sized {
+        Debounce {
+            signal: Some(self),
+            delay: None,
+            ms,
+            leading,
+            trailing: trailing || !leading,
+            pending: None,
+        }
+    }
+
+    /// Spawns the signal and returns a `Future` which resolves with the last value when the signal ends.
+    ///
+    /// The `Future` resolves with `None` if the signal ended without outputting any values.
+    ///
+    /// The signal keeps running even if the `Future` isn't polled, but it is stopped
+    /// when the `Future` is dropped.
+    fn spawn_future(self) -> SpawnFuture<Self::Item>
+        where Self::Item: 'static,
+              Self: Sized + 'static {
+
+        let (sender, receiver) = oneshot::channel();
+
+        let handle = spawn_future(async move {
+            let last = self.to_stream().fold(None, |_, value| ready(Some(value))).await;
+
+            // This is an error if the SpawnFuture was dropped
+            let _ = sender.send(last);
+        });
+
+        SpawnFuture {
+            _handle: handle,
+            receiver,
+        }
+    }
+
+    /// Outputs at most one value per animation frame.
+    ///
+    /// The first value is outputted immediately. After that, all of the changes which
+    /// happen before the next animation frame are merged together, and then the latest
+    /// value is outputted on the next animation frame.
+    ///
+    /// This is useful for signals which update the DOM, because updating the DOM
+    /// more than once per frame is wasted work.
+    #[inline]
+    fn throttle_frames(self) -> ThrottleFrames<Self> where Self: Sized {
+        ThrottleFrames {
+            signal: Some(self),
+            timestamps: None,
+            pending: None,
+            first: true,
+        }
+    }
+
+    /// Outputs a [`Change`] which contains both the previous value and the new value.
+    ///
+    /// For the first change, `old` is `None`.
+    #[inline]
+    fn changes(self) -> Changes<Self> where Self::Item: Clone, Self: Sized {
+        Changes {
+            signal: self,
+            old: None,
+        }
+    }
+
+    /// Outputs the values of `fallback` until this signal changes.
+    ///
+    /// After this signal changes, `fallback` is dropped and only this signal is used.
+    ///
+    /// This is useful for showing placeholder data until the real data is ready.
+    ///
+    /// Unlike [`race`](SignalExtra::race), this signal always wins when it changes,
+    /// even if `fallback` changed first.
+    #[inline]
+    fn or<B>(self, fallback: B) -> Or<Self, B>
+        where B: Signal<Item = Self::Item>,
+              Self: Sized {
+        Or {
+            signal: Some(self),
+            fallback: Some(fallback),
+        }
+    }
+
+    /// Every `ms` milliseconds it outputs the current value of this signal.
+    ///
+    /// The first value of this signal is outputted immediately. After that it outputs
+    /// the value even if this signal hasn't changed, and it doesn't output anything
+    /// when this signal changes.
+    ///
+    /// When this signal ends, the timer is stopped and it ends.
+    #[inline]
+    fn sample_each(self, ms: u32) -> SampleEach<Self> where Self::Item: Clone, Self: Sized {
+        SampleEach {
+            signal: self,
+            ticker: Ticker::new(ms),
+            value: None,
+            first: true,
+        }
+    }
+
+    /// Collapses runs of equal values into `(value, count)`.
+    ///
+    /// When this signal changes to a different value, it outputs the previous value
+    /// and how many times it was repeated. When this signal ends, it outputs the
+    /// final run before ending.
+    ///
+    /// Because the first run hasn't finished yet, the initial value is `None`, and
+    /// after that the runs are outputted as `Some`.
+    #[inline]
+    fn group_consecutive(self) -> GroupConsecutive<Self> where Self::Item: PartialEq, Self: Sized {
+        GroupConsecutive {
+            signal: Some(self),
+            run: None,
+            first: true,
+        }
+    }
+
+    /// Maps this signal to a signal, and then outputs the values of the inner signal.
+    ///
+    /// This is the same as [`switch`](futures_signals::signal::SignalExt::switch).
+    /// Whenever this signal changes, the previous inner signal is dropped.
+    #[inline]
+    fn flat_map<A, F>(self, callback: F) -> Flatten<Map<Self, F>>
+        where A: Signal,
+              F: FnMut(Self::Item) -> A,
+              Self: Sized {
+        self.map(callback).flatten()
+    }
+
+    /// Maps each value of this signal to a `Vec`, and then outputs each element of the `Vec` individually.
+    #[inline]
+    fn flat_map_vec<A, F>(self, callback: F) -> FlatMapVec<Self, A, F>
+        where F: FnMut(Self::Item) -> Vec<A>,
+              Self: Sized {
+        FlatMapVec {
+            signal: Some(self),
+            pending: VecDeque::new(),
+            callback,
+        }
+    }
+
+    /// Combines this signal with another signal.
+    ///
+    /// Whenever either signal changes, `callback` is called with the latest values of
+    /// both signals. Nothing is outputted until both signals have a value.
+    ///
+    /// It ends when both signals have ended.
+    #[inline]
+    fn map2<B, C, F>(self, other: B, callback: F) -> Map2<Self, B, F>
+        where B: Signal,
+              F: FnMut(&Self::Item, &B::Item) -> C,
+              Self: Sized {
+        Map2::new(self, other, callback)
+    }
+
+    /// Allows for using `+`, `-`, `*`, and `/` with signals.
+    ///
+    /// ```rust
+    /// let total = price.signal().ops() * quantity.signal();
+    /// ```
+    ///
+    /// The result is a signal which uses [`map2`](SignalExtra::map2) to combine the
+    /// two signals, so it only outputs a value after both signals have a value.
+    #[inline]
+    fn ops(self) -> SignalOps<Self> where Self: Sized {
+        SignalOps {
+            signal: self,
+        }
+    }
+
+    /// Waits until the browser is idle before outputting the value.
+    ///
+    /// This is useful for low priority updates which shouldn't compete with user
+    /// interaction. If the signal changes multiple times while waiting, only the
+    /// latest value is outputted.
+    ///
+    /// It uses `requestIdleCallback`, or a short timeout if that isn't supported.
+    ///
+    /// When this signal ends, it will wait for the browser to be idle before ending.
+    #[inline]
+    fn debounce_idle(self) -> DebounceIdle<Self> where Self: Sized {
+        DebounceIdle {
+            signal: Some(self),
+            idle: None,
+            pending: None,
+        }
+    }
+
+    /// Converts a signal of `Option<A>` into a signal of `Option<B>`.
+    ///
+    /// `Some` values are passed to `callback`, `None` values are outputted unchanged.
+    ///
+    /// This is the same as `signal.map(|x| x.map(callback))`.
+    #[inline]
+    fn map_option<A, B, F>(self, callback: F) -> MapOption<Self, F>
+        where F: FnMut(A) -> B,
+              Self: Signal<Item = Option<A>> + Sized {
+        MapOption {
+            signal: self,
+            callback,
+        }
+    }
+
+    /// Converts a signal of `Option<A>` into a signal of `Option<B>`.
+    ///
+    /// `Some` values are passed to `callback`, `None` values are outputted unchanged.
+    ///
+    /// This is the same as `signal.map(|x| x.and_then(callback))`.
+    #[inline]
+    fn and_then_option<A, B, F>(self, callback: F) -> AndThenOption<Self, F>
+        where F: FnMut(A) -> Option<B>,
+              Self: Signal<Item = Option<A>> + Sized {
+        AndThenOption {
+            signal: self,
+            callback,
+        }
+    }
+
+    /// Only outputs a value after it has been seen `count` times in a row.
+    ///
+    /// Whenever a different value is seen, it starts counting again. A value is only
+    /// outputted once, even if it continues to be seen after that.
+    ///
+    /// This is useful for ignoring values which flicker.
+    ///
+    /// Because a value might not have settled yet, the initial value is `None`, and
+    /// after that the settled values are outputted as `Some`.
+    ///
+    /// A `count` of `0` behaves the same as `1`.
+    #[inline]
+    fn settle(self, count: usize) -> Settle<Self>
+        where Self::Item: PartialEq + Clone,
+              Self: Sized {
+        Settle {
+            signal: Some(self),
+            count: count.max(1),
+            seen: 0,
+            last: None,
+            first: true,
+        }
+    }
+
+    /// Splits this signal into two signals, each with its own `map` callback.
+    ///
+    /// This signal is only polled once per change, regardless of which of the two
+    /// signals polls it, so it is more efficient than using `broadcast`.
+    ///
+    /// If one of the signals is dropped, the other signal continues to work.
+    #[inline]
+    fn split_map<A, B, F, G>(self, left: F, right: G) -> (SplitMap<Self, F>, SplitMap<Self, G>)
+        where F: FnMut(Self::Item) -> A,
+              G: FnMut(Self::Item) -> B,
+              Self::Item: Clone,
+              Self: Sized {
+        let (left_split, right_split) = SplitHalf::new(self);
+
+        (
+            SplitMap { split: left_split, callback: left },
+            SplitMap { split: right_split, callback: right },
+        )
+    }
+
+    /// Outputs the latest value of this signal whenever `opener` changes.
+    ///
+    /// The changes of this signal are merged together until `opener` changes, then
+    /// the latest value is outputted. If this signal didn't change since the last
+    /// time `opener` changed then nothing is outputted.
+    ///
+    /// This can be used to throttle based on any event, such as animation frames,
+    /// server acknowledgements, or user clicks.
+    ///
+    /// It ends when this signal ends, or when `opener` ends, because nothing more can
+    /// be outputted after that.
+    #[inline]
+    fn throttle_with<B>(self, opener: B) -> ThrottleWith<Self, B>
+        where B: Signal,
+              Self: Sized {
+        ThrottleWith {
+            signal: Some(self),
+            opener: Some(opener),
+            pending: None,
+        }
+    }
+
+    /// Outputs the latest value of this signal whenever this signal changes, and also
+    /// whenever `trigger` changes.
+    ///
+    /// This can be used to force a recompute even though this signal didn't change,
+    /// for example when the user clicks a refresh button.
+    ///
+    /// If this signal and `trigger` change at the same time, the value is only
+    /// outputted once.
+    ///
+    /// It ends when both signals have ended.
+    #[inline]
+    fn repeat_last<B>(self, trigger: B) -> RepeatLast<Self, B>
+        where B: Signal,
+              Self::Item: Clone,
+              Self: Sized {
+        RepeatLast {
+            signal: Some(self),
+            trigger: Some(trigger),
+            value: None,
+        }
+    }
+
+    /// Outputs a version number which increases every time this signal changes.
+    ///
+    /// The first value is `0`. The values of this signal are ignored, so this is a
+    /// cheap way to detect that something changed, for example as a memoization key.
+    #[inline]
+    fn version(self) -> Version<Self> where Self: Sized {
+        Version {
+            signal: self,
+            version: 0,
+        }
+    }
+
+    /// Catches panics which happen while polling this signal.
+    ///
+    /// When a panic is caught, it logs the panic message (and the locations of the
+    /// panic and of the `guarded` call) with `console.error`. After that this signal is dropped
+    /// and it ends, but the signals which depend on it keep working.
+    ///
+    /// This only works if panics unwind. The default for `wasm32-unknown-unknown` is
+    /// `panic = "abort"`, in which case the panic cannot be caught and this does nothing.
+    #[inline]
+    #[track_caller]
+    fn guarded(self) -> Guarded<Self> where Self: Sized {
+        Guarded {
+            signal: Some(self),
+            location: std::panic::Location::caller(),
+        }
+    }
+
+    /// Doesn't output anything until `gate` is `true`.
+    ///
+    /// While `gate` is `false`, the changes of this signal are merged together. When
+    /// `gate` becomes `true` the latest value is outputted, and after that it behaves
+    /// exactly the same as this signal, even if `gate` becomes `false` again.
+    ///
+    /// If `gate` starts as `false`, the initial value is `None`. The values of this
+    /// signal are outputted as `Some`.
+    ///
+    /// This is useful for waiting until the app is initialized.
+    ///
+    /// If `gate` ends without ever being `true`, then this signal also ends.
+    #[inline]
+    fn delay_until<B>(self, gate: B) -> DelayUntil<Self, B>
+        where B: Signal<Item = bool>,
+              Self: Sized {
+        DelayUntil {
+            signal: Some(self),
+            gate: Some(gate),
+            pending: None,
+            first: true,
+        }
+    }
+
+    /// Outputs the average of the last `window` values of this signal.
+    ///
+    /// Before there are `window` values, it outputs the average of the values so far.
+    ///
+    /// This is useful for smoothing noisy values.
+    ///
+    /// A `window` of `0` behaves the same as `1`.
+    #[inline]
+    fn moving_average(self, window: usize) -> MovingAverage<Self>
+        where Self: Signal<Item = f64> + Sized {
+        let window = window.max(1);
+
+        MovingAverage {
+            signal: self,
+            window,
+            values: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Returns a `Future` which resolves with the first value of this signal.
+    ///
+    /// If `ms` milliseconds pass before this signal has a value, then it resolves
+    /// with `None`. It also resolves with `None` if this signal ends without a value.
+    ///
+    /// The timer is cancelled when the `Future` resolves or is dropped.
+    #[inline]
+    fn to_future_timeout(self, ms: u32) -> SignalFutureTimeout<Self> where Self: Sized {
+        SignalFutureTimeout {
+            signal: Some(self),
+            delay: Some(Delay::new(ms)),
+        }
+    }
+
+    /// Outputs a tuple of the time when the value changed, and the value.
+    ///
+    /// The time is in milliseconds, using `performance.now()`.
+    #[inline]
+    fn timestamped(self) -> Timestamped<Self> where Self: Sized {
+        Timestamped {
+            signal: self,
+        }
+    }
+
+    /// Outputs the difference between the new value and the previous value.
+    ///
+    /// Because there is no previous value for the first value, the initial value is
+    /// `None`, and after that the differences are outputted as `Some`.
+    ///
+    /// This is useful for calculating rates of change, such as the scroll delta.
+    #[inline]
+    fn delta(self) -> Delta<Self>
+        where Self::Item: std::ops::Sub + Copy,
+              Self: Sized {
+        Delta {
+            signal: Some(self),
+            previous: None,
+            first: true,
+        }
+    }
+
+    /// Sets `target` to every value of this signal.
+    ///
+    /// This is the same as `for_each(move |value| { target.set(value); async {} })`,
+    /// except it is spawned automatically. The subscription stops if the returned
+    /// [`DropHandle`] is dropped or [`stopped`](DropHandle::stop).
+    fn observe(self, target: Mutable<Self::Item>) -> DropHandle where Self: Sized + 'static {
+        DropHandle {
+            _handle: spawn_future(self.for_each(move |value| {
+                target.set(value);
+                ready(())
+            })),
+        }
+    }
+
+    /// Outputs the exponential moving average of this signal.
+    ///
+    /// For every change it outputs `alpha * value + (1.0 - alpha) * previous`, where
+    /// `previous` is the previous output. The first output is the first value.
+    ///
+    /// A larger `alpha` follows the changes more quickly, a smaller `alpha` is smoother.
+    ///
+    /// `alpha` is clamped between `0.0` and `1.0`.
+    #[inline]
+    fn ema(self, alpha: f64) -> Ema<Self>
+        where Self: Signal<Item = f64> + Sized {
+        Ema {
+            signal: self,
+            alpha: alpha.clamp(0.0, 1.0),
+            previous: None,
+        }
+    }
+
+    /// Behaves exactly the same as this signal, except it ends after `ms` milliseconds.
+    ///
+    /// The timer starts when it is polled for the first time, and it is cancelled if
+    /// this signal ends first.
+    #[inline]
+    fn stop_after(self, ms: u32) -> StopAfter<Self> where Self: Sized {
+        StopAfter {
+            signal: Some(self),
+            delay: Some(Delay::new(ms)),
+        }
+    }
+
+    /// Extracts a value from this signal, and only outputs it if it is different
+    /// from the previous extracted value.
+    ///
+    /// This is useful for binding a single field of a large struct to the DOM:
+    ///
+    /// ```rust
+    /// state.signal_ref(|x| x.clone()).pluck(|state| state.name.clone())
+    /// ```
+    ///
+    /// The values of this signal are passed by reference, so they are never cloned.
+    #[inline]
+    fn pluck<B, F>(self, callback: F) -> Pluck<Self, B, F>
+        where B: PartialEq + Clone,
+              F: FnMut(&Self::Item) -> B,
+              Self: Sized {
+        Pluck {
+            signal: Some(self),
+            callback,
+            previous: None,
+        }
+    }
+
+    /// This is the same as [`flatten`](futures_signals::signal::SignalExt::flatten), except
+    /// `on_switch` is called whenever this signal outputs a new inner signal.
+    ///
+    /// `on_switch` is called before the new inner signal is polled for the first
+    /// time, so it can be used to reset or seed the inner signal.
+    #[inline]
+    fn flatten_with<F>(self, on_switch: F) -> FlattenWith<Self, F>
+        where Self::Item: Signal,
+              F: FnMut(&mut Self::Item),
+              Self: Sized {
+        FlattenWith {
+            signal: Some(self),
+            inner: None,
+            on_switch,
+        }
+    }
+
+    /// Calls `callback` with the values of this signal, but if the value is equal to
+    /// the previous value then `callback` isn't called, and instead the previous
+    /// output is outputted again.
+    ///
+    /// This is useful when `callback` is expensive.
+    ///
+    /// Unlike [`dedupe_map`](futures_signals::signal::SignalExt::dedupe_map), it still
+    /// outputs a value for every change, so it can be used when downstream signals
+    /// need to know about every change.
+    #[inline]
+    fn map_cached<B, F>(self, callback: F) -> MapCached<Self, B, F>
+        where Self::Item: PartialEq,
+              B: Clone,
+              F: FnMut(&Self::Item) -> B,
+              Self: Sized {
+        MapCached {
+            signal: self,
+            callback,
+            cached: None,
+        }
+    }
+
+    /// Converts a signal of `Vec<A>` into a signal of `HashMap<K, A>`, using `key` to
+    /// get the key for each value.
+    ///
+    /// The `HashMap` is rebuilt whenever this signal changes. If multiple values have
+    /// the same key then the last value is used.
+    #[inline]
+    fn index_by<A, K, F>(self, key: F) -> IndexBy<Self, F>
+        where K: Hash + Eq,
+              F: FnMut(&A) -> K,
+              Self: Signal<Item = Vec<A>> + Sized {
+        IndexBy {
+            signal: self,
+            key,
+        }
+    }
+
+    /// Calls `callback` with the first value of this signal.
+    ///
+    /// All of the values are outputted unchanged, including the first value.
+    ///
+    /// This is useful for setup side effects, such as focusing an element.
+    #[inline]
+    fn on_first<F>(self, callback: F) -> OnFirst<Self, F>
+        where F: FnOnce(&Self::Item),
+              Self: Sized {
+        OnFirst {
+            signal: self,
+            callback: Some(callback),
+        }
+    }
+
+    /// This is the same as [`map2`](SignalExtra::map2), except it only outputs a value
+    /// if it is different from the previous value.
+    #[inline]
+    fn map2_dedupe<B, C, F>(self, other: B, callback: F) -> Map2Dedupe<Self, B, C, F>
+        where B: Signal,
+              C: PartialEq + Clone,
+              F: FnMut(&Self::Item, &B::Item) -> C,
+              Self: Sized {
+        Map2Dedupe {
+            signal: Map2::new(self, other, callback),
+            previous: None,
+        }
+    }
+
+    /// Calls `callback` when this signal ends, or when it is dropped, whichever
+    /// happens first.
+    ///
+    /// `callback` is only called once, so this can be used for cleanup which must
+    /// happen regardless of how the signal stops.
+    #[inline]
+    fn finally<F>(self, callback: F) -> Finally<Self, F>
+        where F: FnOnce(),
+              Self: Sized {
+        Finally {
+            signal: self,
+            callback: FinallyCallback(Some(callback)),
+        }
+    }
+
+    /// Outputs the values returned by `callback`, and ends the first time that
+    /// `callback` returns `None`.
+    ///
+    /// This is similar to [`Iterator::map_while`].
+    #[inline]
+    fn map_while<B, F>(self, callback: F) -> MapWhile<Self, F>
+        where F: FnMut(Self::Item) -> Option<B>,
+              Self: Sized {
+        MapWhile {
+            signal: Some(self),
+            callback,
+        }
+    }
+
+    /// Collects all of the values of this signal into a `Vec`, and outputs the `Vec`
+    /// whenever `flush` changes.
+    ///
+    /// If `flush` changes and there aren't any values, then it outputs an empty `Vec`
+    /// if `emit_empty` is `true`, otherwise it doesn't output anything.
+    ///
+    /// It ends when `flush` ends, or when this signal ends and all of its values
+    /// have been outputted.
+    #[inline]
+    fn buffer_until<B>(self, flush: B, emit_empty: bool) -> BufferUntil<Self, B>
+        where B: Signal,
+              Self: Sized {
+        BufferUntil {
+            signal: Some(self),
+            flush: Some(flush),
+            emit_empty,
+            buffer: vec![],
+        }
+    }
+
+    /// This is the same as [`switch`](futures_signals::signal::SignalExt::switch), except
+    /// the inner signal is only created if the key is different from the previous key.
+    ///
+    /// Whenever this signal changes, `key` is called first. If the key is equal to the
+    /// previous key then the existing inner signal is kept and `make` is not called.
+    /// Otherwise `make` is called to create the new inner signal, and the old inner
+    /// signal is dropped.
+    ///
+    /// This avoids expensive resubscriptions when this signal outputs equivalent values.
+    #[inline]
+    fn switch_map_dedupe<K, A, F, G>(self, key: F, make: G) -> SwitchMapDedupe<Self, K, A, F, G>
+        where K: PartialEq,
+              A: Signal,
+              F: FnMut(&Self::Item) -> K,
+              G: FnMut(Self::Item) -> A,
+              Self: Sized {
+        SwitchMapDedupe {
+            signal: Some(self),
+            inner: None,
+            current_key: None,
+            key,
+            make,
+        }
+    }
+
+    /// Calls `callback` whenever this signal is polled and it hasn't changed.
+    ///
+    /// All of the values are outputted unchanged. This is useful for debugging signals
+    /// which seem to be stuck.
+    #[inline]
+    fn inspect_not_changed<F>(self, callback: F) -> InspectNotChanged<Self, F>
+        where F: FnMut(),
+              Self: Sized {
+        InspectNotChanged {
+            signal: self,
+            callback,
+        }
+    }
+
+    /// Outputs the latest value of this signal whenever this signal changes, and also
+    /// every `ms` milliseconds.
+    ///
+    /// This is useful for heartbeats which must happen periodically even if the value
+    /// doesn't change. Nothing is outputted until this signal has a value.
+    ///
+    /// It ends when this signal ends, and the timer is cancelled when it is dropped.
+    #[inline]
+    fn repeat_every(self, ms: u32) -> RepeatEvery<Self>
+        where Self::Item: Clone,
+              Self: Sized {
+        RepeatEvery {
+            signal: Some(self),
+            ticker: Ticker::new(ms),
+            value: None,
+        }
+    }
+
+    /// Outputs at most one value every `ms` milliseconds, using `callback` to map the
+    /// values.
+    ///
+    /// The first value is outputted immediately. After that, the changes which happen
+    /// within `ms` milliseconds are merged together, and the latest value is outputted
+    /// when the time is up.
+    ///
+    /// `callback` is only called for the values which are outputted, so it isn't
+    /// called for the values which are skipped.
+    ///
+    /// When this signal ends, it will wait for the timer before ending.
+    #[inline]
+    fn map_throttled<B, F>(self, ms: u32, callback: F) -> MapThrottled<Self, F>
+        where F: FnMut(Self::Item) -> B,
+              Self: Sized {
+        MapThrottled {
+            signal: Some(self),
+            delay: None,
+            ms,
+            pending: None,
+            callback,
+        }
+    }
+
+    /// Whenever this signal changes, it outputs a tuple of the new value and the latest
+    /// value of `other`.
+    ///
+    /// Changes to `other` don't cause anything to be outputted. If this signal changes
+    /// before `other` has a value, then it waits until `other` has a value.
+    ///
+    /// It ends when this signal ends.
+    #[inline]
+    fn sample_signal<B>(self, other: B) -> SampleSignal<Self, B>
+        where B: Signal,
+              B::Item: Clone,
+              Self: Sized {
+        SampleSignal {
+            signal: self.with_latest_from(other, |value, other| (value, other.clone())),
+        }
+    }
+
+    /// Outputs all of the values of this signal in batches, at most one batch every
+    /// `ms` milliseconds.
+    ///
+    /// The first batch is outputted immediately. After that, all of the values which
+    /// happen within `ms` milliseconds are collected into a `Vec`, which is outputted
+    /// when the time is up. If there weren't any values then nothing is outputted.
+    ///
+    /// Unlike [`map_throttled`](SignalExtra::map_throttled), no values are skipped.
+    ///
+    /// When this signal ends, it will wait for the timer before ending.
+    #[inline]
+    fn throttle_and_collect(self, ms: u32) -> ThrottleAndCollect<Self> where Self: Sized {
+        ThrottleAndCollect {
+            signal: Some(self),
+            delay: None,
+            ms,
+            buffer: vec![],
+        }
+    }
+
+    /// This is the same as `map`, except `callback` also receives `target`.
+    ///
+    /// Only a [`Weak`] reference to `target` is stored, so the signal doesn't keep
+    /// `target` alive. This avoids memory leaks when `target` contains this signal
+    /// (directly or indirectly), which would otherwise create an `Rc` cycle.
+    ///
+    /// If `target` has been dropped then `callback` isn't called and nothing is
+    /// outputted.
+    #[inline]
+    fn map_weak<A, B, F>(self, target: &Rc<A>, callback: F) -> MapWeak<Self, A, F>
+        where F: FnMut(&A, Self::Item) -> B,
+              Self: Sized {
+        MapWeak {
+            signal: self,
+            target: Rc::downgrade(target),
+            callback,
+        }
+    }
+
+    /// Returns a `Future` which resolves with the first `n` values of this signal.
+    ///
+    /// If this signal ends before it has `n` values, then it resolves with the values
+    /// that it has. It stops polling this signal as soon as it has `n` values.
+    #[inline]
+    fn first_n(self, n: usize) -> FirstN<Self> where Self: Sized {
+        FirstN {
+            signal: Some(self),
+            n,
+            values: Vec::with_capacity(n),
+        }
+    }
+
+    /// Combines two signals of `Option`.
+    ///
+    /// Whenever either signal changes, if both of the latest values are `Some` then
+    /// it outputs `Some` with the result of `callback`, otherwise it outputs `None`.
+    ///
+    /// This is useful for combining two values which are loaded independently.
+    ///
+    /// Like [`map2`](SignalExtra::map2), nothing is outputted until both signals have
+    /// a value, and it ends when both signals have ended.
+    #[inline]
+    fn map2_option<A, B, C, D, F>(self, other: B, callback: F) -> Map2Option<Self, B, F>
+        where B: Signal<Item = Option<C>>,
+              F: FnMut(&A, &C) -> D,
+              Self: Signal<Item = Option<A>> + Sized {
+        Map2Option {
+            left: Some(self),
+            right: Some(other),
+            left_value: None,
+            right_value: None,
+            callback,
+        }
+    }
+
+    /// Converts a signal of functions which return a `Future` into a signal of the
+    /// results of the `Future`s, retrying the failed `Future`s with exponential backoff.
+    ///
+    /// Whenever this signal changes, the function is called and the `Future` is run.
+    /// If the `Future` returns `Ok` then the value is outputted immediately. If it
+    /// returns `Err` then it waits `base_ms * 2^attempt` milliseconds and then calls
+    /// the function again. After `max` failed attempts it outputs the `Err`.
+    ///
+    /// ```rust
+    /// id.signal().map(|id| move || fetch_user(id)).retry_with_backoff(3, 100)
+    /// ```
+    ///
+    /// The attempt counter is reset when this signal changes, and after a successful
+    /// attempt. If this signal changes while it is retrying, then the old `Future` is
+    /// cancelled.
+    ///
+    /// The delay doesn't include any random jitter.
+    #[inline]
+    fn retry_with_backoff<A, E, F, Fut>(self, max: usize, base_ms: u32) -> RetryWithBackoff<Self, Fut>
+        where F: FnMut() -> Fut,
+              Fut: Future<Output = Result<A, E>>,
+              Self: Signal<Item = F> + Sized {
+        RetryWithBackoff {
+            signal: Some(self),
+            make: None,
+            future: None,
+            delay: None,
+            attempt: 0,
+            max,
+            base_ms,
+        }
+    }
+
+    /// Splits a signal of `bool` into two signals which output `Some(())` when the value
+    /// changes from `false` to `true` (rising), and when it changes from `true` to
+    /// `false` (falling).
+    ///
+    /// The initial value of this signal isn't a change, so the initial value of both
+    /// signals is `None`. If the value changes multiple times before a signal is
+    /// polled, it is only outputted once.
+    ///
+    /// Like [`split_map`](SignalExtra::split_map), this signal is only polled once per
+    /// change, regardless of which of the two signals polls it.
+    #[inline]
+    fn split_edges(self) -> (Edge<Self>, Edge<Self>) where Self: Signal<Item = bool> + Sized {
+        let (rising, falling) = SplitHalf::new(Edges { signal: self, previous: None });
+        (Edge { split: rising, first: true }, Edge { split: falling, first: true })
+    }
+
+    /// Whenever this signal changes, it calls `callback` with the new value and the
+    /// latest value of `other`, and outputs the result.
+    ///
+    /// Unlike [`map2`](SignalExtra::map2), changes to `other` don't cause anything to
+    /// be outputted. If this signal changes before `other` has a value, then it waits
+    /// until `other` has a value.
+    ///
+    /// It ends when this signal ends.
+    #[inline]
+    fn with_latest_from<B, C, F>(self, other: B, callback: F) -> WithLatestFrom<Self, B, F>
+        where B: Signal,
+              F: FnMut(Self::Item, &B::Item) -> C,
+              Self: Sized {
+        WithLatestFrom {
+            signal: Some(self),
+            other: Some(other),
+            pending: None,
+            other_value: None,
+            callback,
+        }
+    }
+
+    /// Only outputs a value if it differs from the previously outputted value by at
+    /// least `epsilon`.
+    ///
+    /// The comparison is with the previously outputted value, not the previous value,
+    /// so many small changes still add up to a change which is outputted.
+    #[inline]
+    fn dedupe_epsilon(self, epsilon: f64) -> DedupeEpsilon<Self>
+        where Self: Signal<Item = f64> + Sized {
+        DedupeEpsilon {
+            signal: Some(self),
+            epsilon,
+            previous: None,
+        }
+    }
+}
+
+impl<S: Signal> SignalExtra for S {}
+
+
+/// Stops a signal subscription when it is dropped.
+///
+/// This is returned by methods such as [`SignalExtra::for_each_try`].
+#[must_use = "The subscription is stopped when the DropHandle is dropped"]
+pub struct DropHandle {
+    _handle: DiscardOnDrop<CancelableFutureHandle>,
+}
+
+impl DropHandle {
+    /// Stops the subscription, this is the same as dropping the `DropHandle`.
+    #[inline]
+    pub fn stop(self) {}
+
+    /// Stops all of the subscriptions.
+    ///
+    /// Dropping a `Vec<DropHandle>` will also stop all of the subscriptions.
+    #[inline]
+    pub fn stop_all<I>(handles: I) where I: IntoIterator<Item = DropHandle> {
+        for handle in handles {
+            handle.stop();
+        }
+    }
+}
+
+impl fmt::Debug for DropHandle {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_tuple("DropHandle")
+            .finish()
+    }
+}
+
+
+#[pin_project(project = MapIndexProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapIndex<S, K, B, F, T> {
+    #[pin]
+    signal: S,
+    cache: HashMap<K, B>,
+    key: F,
+    transform: T,
+}
+
+impl<A, B, K, S, F, T> Signal for MapIndex<S, K, B, F, T>
+    where S: Signal<Item = Vec<A>>,
+          K: Eq + Hash,
+          B: Clone,
+          F: FnMut(&A) -> K,
+          T: FnMut(A) -> B {
+    type Item = Vec<B>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("MapIndex", {
+            let MapIndexProj { signal, cache, key, transform } = self.project();
+
+            signal.poll_change(cx).map(|values| {
+                values.map(|values| {
+                    let mut old_cache = std::mem::replace(cache, HashMap::with_capacity(values.len()));
+
+                    values.into_iter().map(|value| {
+                        let key = key(&value);
+
+                        if let Some(output) = cache.get(&key) {
+                            output.clone()
+
+                        } else {
+                            let output = match old_cache.remove(&key) {
+                                Some(output) => output,
+                                None => transform(value),
+                            };
+
+                            cache.insert(key, output.clone());
+
+                            output
+                        }
+                    }).collect()
+                })
+            })
+        })
+    }
+}
+
+
+#[pin_project(project = ElapsedProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Elapsed<S> {
+    #[pin]
+    signal: Option<S>,
+    ticker: Ticker,
+    last_change: Option<f64>,
+}
+
+impl<S> Signal for Elapsed<S> where S: Signal {
+    type Item = f64;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Elapsed", {
+            let ElapsedProj { mut signal, ticker, last_change } = self.project();
+
+            let mut changed = false;
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(_))) => {
+                        changed = true;
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            let ticked = ticker.poll_tick(cx).is_ready();
+
+            if changed || last_change.is_none() {
+                *last_change = Some(bindings::performance_now());
+                Poll::Ready(Some(0.0))
+
+            } else {
+                match last_change {
+                    Some(last_change) if ticked => Poll::Ready(Some(bindings::performance_now() - *last_change)),
+                    _ => Poll::Pending,
+                }
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = TweenProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Tween<S> {
+    #[pin]
+    signal: Option<S>,
+    duration: f64,
+    timestamps: Option<Timestamps>,
+    current: Option<f64>,
+    start: f64,
+    end: f64,
+    start_time: Option<f64>,
+}
+
+impl<S> Signal for Tween<S> where S: Signal<Item = f64> {
+    type Item = f64;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Tween", {
+            let TweenProj { mut signal, duration, timestamps, current, start, end, start_time } = self.project();
+
+            let mut changed = None;
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        changed = Some(value);
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            if let Some(value) = changed {
+                match *current {
+                    Some(old) if *duration > 0.0 => {
+                        if value != *end || timestamps.is_some() {
+                            *start = old;
+                            *end = value;
+                            *start_time = None;
+
+                            if timestamps.is_none() {
+                                *timestamps = Some(animation::timestamps());
+                            }
+                        }
+                    },
+                    _ => {
+                        *current = Some(value);
+                        *end = value;
+                        *timestamps = None;
+                        return Poll::Ready(Some(value));
+                    },
+                }
+            }
+
+            if let Some(frames) = timestamps {
+                loop {
+                    match frames.poll_change_unpin(cx) {
+                        Poll::Ready(Some(Some(time))) => {
+                            let start_time = *start_time.get_or_insert(time);
+                            let percentage = (time - start_time) / *duration;
+
+                            let value = if percentage >= 1.0 {
+                                *timestamps = None;
+                                *end
+
+                            } else {
+                                *start + ((*end - *start) * percentage)
+                            };
+
+                            *current = Some(value);
+                            return Poll::Ready(Some(value));
+                        },
+                        Poll::Ready(Some(None)) => {
+                            continue;
+                        },
+                        Poll::Ready(None) | Poll::Pending => {
+                            return Poll::Pending;
+                        },
+                    }
+                }
+
+            } else if signal.is_none() {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = UnwrapOrElseProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct UnwrapOrElse<S, F> {
+    #[pin]
+    signal: S,
+    callback: F,
+}
+
+impl<A, E, S, F> Signal for UnwrapOrElse<S, F>
+    where S: Signal<Item = Result<A, E>>,
+          F: FnMut(E) -> A {
+    type Item = A;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("UnwrapOrElse", {
+            let UnwrapOrElseProj { signal, callback } = self.project();
+
+            signal.poll_change(cx).map(|value| value.map(|value| value.unwrap_or_else(callback)))
+        })
+    }
+}
+
+
+#[pin_project(project = RaceProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Race<A, B> {
+    #[pin]
+    left: Option<A>,
+    #[pin]
+    right: Option<B>,
+}
+
+impl<A, B> Signal for Race<A, B>
+    where A: Signal,
+          B: Signal<Item = A::Item> {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Race", {
+            let RaceProj { mut left, mut right } = self.project();
+
+            let is_racing = left.is_some() && right.is_some();
+
+            match left.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                Some(Poll::Ready(Some(value))) => {
+                    right.set(None);
+                    return Poll::Ready(Some(value));
+                },
+                Some(Poll::Ready(None)) => {
+                    left.set(None);
+                },
+                Some(Poll::Pending) if !is_racing => {
+                    return Poll::Pending;
+                },
+                Some(Poll::Pending) | None => {},
+            }
+
+            match right.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                Some(Poll::Ready(Some(value))) => {
+                    left.set(None);
+                    Poll::Ready(Some(value))
+                },
+                Some(Poll::Ready(None)) => {
+                    right.set(None);
+
+                    if left.is_some() {
+                        Poll::Pending
+
+                    } else {
+                        Poll::Ready(None)
+                    }
+                },
+                Some(Poll::Pending) => Poll::Pending,
+                None => if left.is_some() {
+                    Poll::Pending
+
+                } else {
+                    Poll::Ready(None)
+                },
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = DebounceProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Debounce<S> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    delay: Option<Delay>,
+    ms: u32,
+    leading: bool,
+    trailing: bool,
+    pending: Option<S::Item>,
+}
+
+impl<S> Signal for Debounce<S> where S: Signal {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Debounce", {
+            let DebounceProj { mut signal, delay, ms, leading, trailing, pending } = self.project();
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        let is_idle = delay.is_none();
+
+                        // Restarts the timer
+                        *delay = Some(Delay::new(*ms));
+
+                        if is_idle && *leading {
+                            let _ = delay.as_mut().unwrap_throw().poll_delay(cx);
+                            return Poll::Ready(Some(value));
+
+                        } else if *trailing {
+                            *pending = Some(value);
+                        }
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            if let Some(timer) = delay {
+                if timer.poll_delay(cx).is_ready() {
+                    *delay = None;
+
+                    if let Some(value) = pending.take() {
+                        return Poll::Ready(Some(value));
+                    }
+
+                } else {
+                    return Poll::Pending;
+                }
+            }
+
+            if signal.is_none() {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+}
+
+
+/// This is returned by the [`SignalExtra::spawn_future`] method.
+#[must_use = "Futures do nothing unless polled"]
+pub struct SpawnFuture<A> {
+    _handle: DiscardOnDrop<CancelableFutureHandle>,
+    receiver: oneshot::Receiver<Option<A>>,
+}
+
+impl<A> Future for SpawnFuture<A> {
+    type Output = Option<A>;
+
+    #[inline]
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        self.receiver.poll_unpin(cx).map(|value| value.unwrap_or(None))
+    }
+}
+
+impl<A> fmt::Debug for SpawnFuture<A> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("SpawnFuture")
+            .finish()
+    }
+}
+
+
+#[pin_project(project = ThrottleFramesProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct ThrottleFrames<S> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    timestamps: Option<Timestamps>,
+    pending: Option<S::Item>,
+    first: bool,
+}
+
+impl<S> Signal for ThrottleFrames<S> where S: Signal {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("ThrottleFrames", {
+            let ThrottleFramesProj { mut signal, timestamps, pending, first } = self.project();
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        *pending = Some(value);
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            if *first && pending.is_some() {
+                *first = false;
+                Poll::Ready(pending.take())
+
+            } else if pending.is_some() {
+                let frames = timestamps.get_or_insert_with(animation::timestamps);
+
+                loop {
+                    match frames.poll_change_unpin(cx) {
+                        Poll::Ready(Some(Some(_))) => {
+                            // Stops the animation frame loop until there is a new value
+                            *timestamps = None;
+                            return Poll::Ready(pending.take());
+                        },
+                        Poll::Ready(Some(None)) => {
+                            continue;
+                        },
+                        Poll::Ready(None) | Poll::Pending => {
+                            return Poll::Pending;
+                        },
+                    }
+                }
+
+            } else if signal.is_none() {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+}
+
+
+/// This is outputted by the [`SignalExtra::changes`] method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Change<A> {
+    pub old: Option<A>,
+    pub new: A,
+}
+
+#[pin_project(project = ChangesProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Changes<S> where S: Signal {
+    #[pin]
+    signal: S,
+    old: Option<S::Item>,
+}
+
+impl<S> Signal for Changes<S> where S: Signal, S::Item: Clone {
+    type Item = Change<S::Item>;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Changes", {
+            let ChangesProj { signal, old } = self.project();
+
+            signal.poll_change(cx).map(|value| {
+                value.map(|new| {
+                    Change {
+                        old: old.replace(new.clone()),
+                        new,
+                    }
+                })
+            })
+        })
+    }
+}
+
+
+#[pin_project(project = OrProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Or<A, B> {
+    #[pin]
+    signal: Option<A>,
+    #[pin]
+    fallback: Option<B>,
+}
+
+impl<A, B> Signal for Or<A, B>
+    where A: Signal,
+          B: Signal<Item = A::Item> {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Or", {
+            let OrProj { mut signal, mut fallback } = self.project();
+
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                Some(Poll::Ready(Some(value))) => {
+                    fallback.set(None);
+                    return Poll::Ready(Some(value));
+                },
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                },
+                Some(Poll::Pending) | None => {},
+            }
+
+            match fallback.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                Some(Poll::Ready(Some(value))) => Poll::Ready(Some(value)),
+                Some(Poll::Ready(None)) => {
+                    fallback.set(None);
+
+                    if signal.is_some() {
+                        Poll::Pending
+
+                    } else {
+                        Poll::Ready(None)
+                    }
+                },
+                Some(Poll::Pending) => Poll::Pending,
+                None => if signal.is_some() {
+                    Poll::Pending
+
+                } else {
+                    Poll::Ready(None)
+                },
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = SampleEachProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct SampleEach<S> where S: Signal {
+    #[pin]
+    signal: S,
+    ticker: Ticker,
+    value: Option<S::Item>,
+    first: bool,
+}
+
+impl<S> Signal for SampleEach<S> where S: Signal, S::Item: Clone {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("SampleEach", {
+            let SampleEachProj { mut signal, ticker, value, first } = self.project();
+
+            loop {
+                match signal.as_mut().poll_change(cx) {
+                    Poll::Ready(Some(new_value)) => {
+                        *value = Some(new_value);
+                    },
+                    Poll::Ready(None) => {
+                        return Poll::Ready(None);
+                    },
+                    Poll::Pending => {
+                        break;
+                    },
+                }
+            }
+
+            if *first {
+                if let Some(value) = value {
+                    *first = false;
+                    return Poll::Ready(Some(value.clone()));
+                }
+            }
+
+            loop {
+                if ticker.poll_tick(cx).is_pending() {
+                    return Poll::Pending;
+                }
+
+                if let Some(value) = value {
+                    return Poll::Ready(Some(value.clone()));
+                }
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = GroupConsecutiveProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct GroupConsecutive<S> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    run: Option<(S::Item, usize)>,
+    first: bool,
+}
+
+impl<S> Signal for GroupConsecutive<S> where S: Signal, S::Item: PartialEq {
+    type Item = Option<(S::Item, usize)>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("GroupConsecutive", {
+            let GroupConsecutiveProj { mut signal, run, first } = self.project();
+
+            let poll = loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        match run {
+                            Some((old, count)) if *old == value => {
+                                *count += 1;
+                            },
+                            Some(_) => {
+                                break Poll::Ready(run.replace((value, 1)));
+                            },
+                            None => {
+                                *run = Some((value, 1));
+                            },
+                        }
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                    },
+                    Some(Poll::Pending) => {
+                        break Poll::Pending;
+                    },
+                    None => {
+                        break Poll::Ready(run.take());
+                    },
+                }
+            };
+
+            poll_first(first, poll)
+        })
+    }
+}
+
+
+#[pin_project(project = FlatMapVecProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct FlatMapVec<S, A, F> {
+    #[pin]
+    signal: Option<S>,
+    pending: VecDeque<A>,
+    callback: F,
+}
+
+impl<S, A, F> Signal for FlatMapVec<S, A, F>
+    where S: Signal,
+          F: FnMut(S::Item) -> Vec<A> {
+    type Item = A;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("FlatMapVec", {
+            let FlatMapVecProj { mut signal, pending, callback } = self.project();
+
+            loop {
+                if let Some(value) = pending.pop_front() {
+                    return Poll::Ready(Some(value));
+                }
+
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        *pending = callback(value).into();
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                    },
+                    Some(Poll::Pending) => {
+                        return Poll::Pending;
+                    },
+                    None => {
+                        return Poll::Ready(None);
+                    },
+                }
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = Map2Proj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Map2<A, B, F> where A: Signal, B: Signal {
+    #[pin]
+    left: Option<A>,
+    #[pin]
+    right: Option<B>,
+    left_value: Option<A::Item>,
+    right_value: Option<B::Item>,
+    callback: F,
+}
+
+impl<A, B, F> Map2<A, B, F> where A: Signal, B: Signal {
+    #[inline]
+    fn new(left: A, right: B, callback: F) -> Self {
+        Self {
+            left: Some(left),
+            right: Some(right),
+            left_value: None,
+            right_value: None,
+            callback,
+        }
+    }
+}
+
+impl<A, B, C, F> Signal for Map2<A, B, F>
+    where A: Signal,
+          B: Signal,
+          F: FnMut(&A::Item, &B::Item) -> C {
+    type Item = C;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Map2", {
+            let Map2Proj { left, right, left_value, right_value, callback } = self.project();
+
+            poll_map2(cx, left, right, left_value, right_value).map(|changed| {
+                changed.map(|()| callback(left_value.as_ref().unwrap_throw(), right_value.as_ref().unwrap_throw()))
+            })
+        })
+    }
+}
+
+/// Polls both signals and stores their latest values.
+///
+/// It returns `Ready(Some(()))` if either signal changed and both signals have a value,
+/// or `Ready(None)` if it can never output a value again.
+fn poll_map2<A, B>(
+    cx: &mut Context,
+    mut left: Pin<&mut Option<A>>,
+    mut right: Pin<&mut Option<B>>,
+    left_value: &mut Option<A::Item>,
+    right_value: &mut Option<B::Item>,
+) -> Poll<Option<()>> where A: Signal, B: Signal {
+    let mut changed = false;
+
+    match left.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+        Some(Poll::Ready(Some(value))) => {
+            *left_value = Some(value);
+            changed = true;
+        },
+        Some(Poll::Ready(None)) => {
+            left.set(None);
+        },
+        Some(Poll::Pending) | None => {},
+    }
+
+    match right.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+        Some(Poll::Ready(Some(value))) => {
+            *right_value = Some(value);
+            changed = true;
+        },
+        Some(Poll::Ready(None)) => {
+            right.set(None);
+        },
+        Some(Poll::Pending) | None => {},
+    }
+
+    if changed && left_value.is_some() && right_value.is_some() {
+        Poll::Ready(Some(()))
+
+    } else {
+        let left_done = left.is_none() && (right.is_none() || left_value.is_none());
+        let right_done = right.is_none() && (left.is_none() || right_value.is_none());
+
+        if left_done || right_done {
+            Poll::Ready(None)
+
+        } else {
+            Poll::Pending
+        }
+    }
+}
+
+
+/// This is returned by the [`SignalExtra::ops`] method.
+#[pin_project(project = SignalOpsProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct SignalOps<S> {
+    #[pin]
+    signal: S,
+}
+
+impl<S> Signal for SignalOps<S> where S: Signal {
+    type Item = S::Item;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("SignalOps", {
+            self.project().signal.poll_change(cx)
+        })
+    }
+}
+
+macro_rules! signal_ops {
+    ($name:ident, $method:ident) => {
+        impl<A, B> std::ops::$name<B> for SignalOps<A>
+            where A: Signal,
+                  B: Signal,
+                  A::Item: std::ops::$name<B::Item> + Clone,
+                  B::Item: Clone {
+
+            type Output = SignalOps<Map2<A, B, fn(&A::Item, &B::Item) -> <A::Item as std::ops::$name<B::Item>>::Output>>;
+
+            #[inline]
+            fn $method(self, other: B) -> Self::Output {
+                SignalOps {
+                    signal: Map2::new(self.signal, other, |left, right| std::ops::$name::$method(left.clone(), right.clone())),
+                }
+            }
+        }
+    };
+}
+
+signal_ops!(Add, add);
+signal_ops!(Sub, sub);
+signal_ops!(Mul, mul);
+signal_ops!(Div, div);
+
+
+#[pin_project(project = DebounceIdleProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct DebounceIdle<S> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    idle: Option<Idle>,
+    pending: Option<S::Item>,
+}
+
+impl<S> Signal for DebounceIdle<S> where S: Signal {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("DebounceIdle", {
+            let DebounceIdleProj { mut signal, idle, pending } = self.project();
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        *pending = Some(value);
+
+                        if idle.is_none() {
+                            *idle = Some(Idle::new());
+                        }
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            if let Some(callback) = idle {
+                if callback.poll_idle(cx).is_ready() {
+                    *idle = None;
+
+                    if let Some(value) = pending.take() {
+                        return Poll::Ready(Some(value));
+                    }
+
+                } else {
+                    return Poll::Pending;
+                }
+            }
+
+            if signal.is_none() {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = MapOptionProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapOption<S, F> {
+    #[pin]
+    signal: S,
+    callback: F,
+}
+
+impl<A, B, S, F> Signal for MapOption<S, F>
+    where S: Signal<Item = Option<A>>,
+          F: FnMut(A) -> B {
+    type Item = Option<B>;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("MapOption", {
+            let MapOptionProj { signal, callback } = self.project();
+
+            signal.poll_change(cx).map(|value| value.map(|value| value.map(callback)))
+        })
+    }
+}
+
+
+#[pin_project(project = AndThenOptionProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct AndThenOption<S, F> {
+    #[pin]
+    signal: S,
+    callback: F,
+}
+
+impl<A, B, S, F> Signal for AndThenOption<S, F>
+    where S: Signal<Item = Option<A>>,
+          F: FnMut(A) -> Option<B> {
+    type Item = Option<B>;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("AndThenOption", {
+            let AndThenOptionProj { signal, callback } = self.project();
+
+            signal.poll_change(cx).map(|value| value.map(|value| value.and_then(callback)))
+        })
+    }
+}
+
+
+#[pin_project(project = SettleProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Settle<S> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    count: usize,
+    seen: usize,
+    last: Option<S::Item>,
+    first: bool,
+}
+
+impl<S> Signal for Settle<S> where S: Signal, S::Item: PartialEq + Clone {
+    type Item = Option<S::Item>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Settle", {
+            let SettleProj { mut signal, count, seen, last, first } = self.project();
+
+            let poll = loop {
+                break match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        if last.as_ref() == Some(&value) {
+                            *seen += 1;
+
+                        } else {
+                            *seen = 1;
+                            *last = Some(value);
+                        }
+
+                        if *seen == *count {
+                            Poll::Ready(last.clone())
+
+                        } else {
+                            continue;
+                        }
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        Poll::Ready(None)
+                    },
+                    Some(Poll::Pending) => Poll::Pending,
+                    None => Poll::Ready(None),
+                };
+            };
+
+            poll_first(first, poll)
+        })
+    }
+}
+
+
+/// Creates a `Signal` which outputs the value of a `oneshot` channel.
+///
+/// This is the same as [`from_future`](futures_signals::signal::from_future): the initial
+/// value is `None`, and when the `oneshot` resolves it outputs `Some(value)` and ends.
+///
+/// If the `Sender` is dropped without sending a value then it ends without
+/// outputting `Some`.
+#[inline]
+pub fn from_oneshot<A>(receiver: oneshot::Receiver<A>) -> FromOneshot<A> {
+    FromOneshot {
+        receiver: Some(receiver),
+        first: true,
+    }
+}
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct FromOneshot<A> {
+    receiver: Option<oneshot::Receiver<A>>,
+    first: bool,
+}
+
+impl<A> Unpin for FromOneshot<A> {}
+
+impl<A> Signal for FromOneshot<A> {
+    type Item = Option<A>;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("FromOneshot", {
+            let this = &mut *self;
+
+            let poll = match this.receiver.as_mut().map(|receiver| receiver.poll_unpin(cx)) {
+                Some(Poll::Ready(value)) => {
+                    this.receiver = None;
+                    Poll::Ready(value.ok())
+                },
+                Some(Poll::Pending) => Poll::Pending,
+                None => Poll::Ready(None),
+            };
+
+            poll_first(&mut this.first, poll)
+        })
+    }
+}
+
+
+/// The state which is shared by the two halves of a split signal.
+#[derive(Debug)]
+struct SplitState<S, T> {
+    signal: Option<Pin<Box<S>>>,
+    values: [Option<T>; 2],
+    wakers: [Option<Waker>; 2],
+}
+
+/// One of the two halves of a split signal.
+///
+/// The input signal is polled by whichever half is polled first, and the values are
+/// stored until the other half is polled.
+#[derive(Debug)]
+struct SplitHalf<S, T> {
+    state: Rc<RefCell<SplitState<S, T>>>,
+    index: usize,
+}
+
+impl<S, T> SplitHalf<S, T> {
+    fn new(signal: S) -> (Self, Self) {
+        let state = Rc::new(RefCell::new(SplitState {
+            signal: Some(Box::pin(signal)),
+            values: [None, None],
+            wakers: [None, None],
+        }));
+
+        (
+            SplitHalf { state: state.clone(), index: 0 },
+            SplitHalf { state, index: 1 },
+        )
+    }
+
+    /// Polls the input signal, calling `split` with each of its values so that it can
+    /// store them for the two halves, then takes the value for this half.
+    fn poll<F>(&self, cx: &mut Context, mut split: F) -> Poll<Option<T>>
+        where S: Signal,
+              F: FnMut(S::Item, &mut [Option<T>; 2]) {
+        let (waker, value, done) = {
+            let mut state = self.state.borrow_mut();
+            let state = &mut *state;
+            let mut changed = false;
+
+            loop {
+                match state.signal.as_mut().map(|signal| signal.as_mut().poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        split(value, &mut state.values);
+                        changed = true;
+                    },
+                    Some(Poll::Ready(None)) => {
+                        state.signal = None;
+                        changed = true;
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            state.wakers[self.index] = Some(cx.waker().clone());
+
+            let waker = if changed {
+                state.wakers[1 - self.index].take()
+
+            } else {
+                None
+            };
+
+            (waker, state.values[self.index].take(), state.signal.is_none())
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+
+        match value {
+            Some(value) => Poll::Ready(Some(value)),
+            None => if done {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            },
+        }
+    }
+}
+
+impl<S, T> Drop for SplitHalf<S, T> {
+    fn drop(&mut self) {
+        let waker = self.state.borrow_mut().wakers[1 - self.index].take();
+
+        // The other signal might be waiting for this signal to poll
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+
+/// This is returned by the [`SignalExtra::split_map`] method.
+#[must_use = "Signals do nothing unless polled"]
+pub struct SplitMap<S, F> where S: Signal {
+    split: SplitHalf<S, S::Item>,
+    callback: F,
+}
+
+impl<S, F> Unpin for SplitMap<S, F> where S: Signal {}
+
+impl<S, F> fmt::Debug for SplitMap<S, F> where S: Signal {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("SplitMap")
+            .field("index", &self.split.index)
+            .finish()
+    }
+}
+
+impl<A, S, F> Signal for SplitMap<S, F>
+    where S: Signal,
+          S::Item: Clone,
+          F: FnMut(S::Item) -> A {
+    type Item = A;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("SplitMap", {
+            let this = &mut *self;
+
+            this.split.poll(cx, |value, values| {
+                values[0] = Some(value.clone());
+                values[1] = Some(value);
+            }).map(|value| value.map(&mut this.callback))
+        })
+    }
+}
+
+
+#[pin_project(project = ThrottleWithProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct ThrottleWith<S, B> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    #[pin]
+    opener: Option<B>,
+    pending: Option<S::Item>,
+}
+
+impl<S, B> Signal for ThrottleWith<S, B> where S: Signal, B: Signal {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("ThrottleWith", {
+            let ThrottleWithProj { mut signal, mut opener, pending } = self.project();
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        *pending = Some(value);
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            let mut opened = false;
+
+            loop {
+                match opener.as_mut().as_pin_mut().map(|opener| opener.poll_change(cx)) {
+                    Some(Poll::Ready(Some(_))) => {
+                        opened = true;
+                    },
+                    Some(Poll::Ready(None)) => {
+                        opener.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            if opened && pending.is_some() {
+                Poll::Ready(pending.take())
+
+            } else if opener.is_none() || (signal.is_none() && pending.is_none()) {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = RepeatLastProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct RepeatLast<S, B> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    #[pin]
+    trigger: Option<B>,
+    value: Option<S::Item>,
+}
+
+impl<S, B> Signal for RepeatLast<S, B> where S: Signal, S::Item: Clone, B: Signal {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("RepeatLast", {
+            let RepeatLastProj { mut signal, mut trigger, value } = self.project();
+
+            let mut changed = false;
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(new_value))) => {
+                        *value = Some(new_value);
+                        changed = true;
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            loop {
+                match trigger.as_mut().as_pin_mut().map(|trigger| trigger.poll_change(cx)) {
+                    Some(Poll::Ready(Some(_))) => {
+                        changed = true;
+                    },
+                    Some(Poll::Ready(None)) => {
+                        trigger.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            if changed && value.is_some() {
+                Poll::Ready(value.clone())
+
+            } else if signal.is_none() && (trigger.is_none() || value.is_none()) {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = VersionProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Version<S> {
+    #[pin]
+    signal: S,
+    version: u64,
+}
+
+impl<S> Signal for Version<S> where S: Signal {
+    type Item = u64;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Version", {
+            let VersionProj { signal, version } = self.project();
+
+            signal.poll_change(cx).map(|value| value.map(|_| {
+                let current = *version;
+                *version += 1;
+                current
+            }))
+        })
+    }
+}
+
+
+thread_local! {
+    // How many guarded signals are currently being polled on this thread
+    static GUARDED_DEPTH: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+    static PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// The location of a panic is only available to the panic hook, so this installs a panic hook
+// (once) which records the location while a guarded signal is being polled on this thread.
+// Every other panic is passed to the previous hook.
+fn install_panic_hook() {
+    static INSTALL: std::sync::Once = std::sync::Once::new();
+
+    // `set_hook` panics if it is called while panicking
+    if std::thread::panicking() {
+        return;
+    }
+
+    INSTALL.call_once(|| {
+        let previous = std::panic::take_hook();
+
+        std::panic::set_hook(Box::new(move |info| {
+            let guarded = GUARDED_DEPTH.try_with(|depth| depth.get() > 0).unwrap_or(false);
+
+            if guarded {
+                let _ = PANIC_LOCATION.try_with(|location| {
+                    *location.borrow_mut() = info.location().map(|location| location.to_string());
+                });
+
+            } else {
+                previous(info);
+            }
+        }));
+    });
+}
+
+/// Like `catch_unwind`, except it also returns the location of the panic.
+///
+/// The location is `None` if it isn't known, for example if another panic hook was
+/// installed afterwards.
+fn catch_unwind_with_location<A, F>(f: F) -> Result<A, (Box<dyn std::any::Any + Send>, Option<String>)>
+    where F: FnOnce() -> A {
+    install_panic_hook();
+
+    GUARDED_DEPTH.with(|depth| depth.set(depth.get() + 1));
+    let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+    GUARDED_DEPTH.with(|depth| depth.set(depth.get() - 1));
+
+    result.map_err(|payload| {
+        let location = PANIC_LOCATION.with(|location| location.borrow_mut().take());
+        (payload, location)
+    })
+}
+
+#[pin_project(project = GuardedProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Guarded<S> {
+    #[pin]
+    signal: Option<S>,
+    location: &'static std::panic::Location<'static>,
+}
+
+impl<S> Signal for Guarded<S> where S: Signal {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Guarded", {
+            let GuardedProj { mut signal, location } = self.project();
+
+            let result = match signal.as_mut().as_pin_mut() {
+                Some(inner) => catch_unwind_with_location(move || inner.poll_change(cx)),
+                None => return Poll::Ready(None),
+            };
+
+            match result {
+                Ok(poll) => poll,
+                Err((payload, panic_location)) => {
+                    let message = payload.downcast_ref::<&str>().copied()
+                        .or_else(|| payload.downcast_ref::<String>().map(|message| message.as_str()))
+                        .unwrap_or("<unknown>");
+
+                    let panic_location = panic_location.as_deref().unwrap_or("<unknown>");
+
+                    bindings::console_error(&format!("Signal panicked at {}: {}\n    guarded at {}", panic_location, message, location));
+
+                    // The signal might be in an invalid state after the panic
+                    signal.set(None);
+
+                    Poll::Ready(None)
+                },
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = DelayUntilProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct DelayUntil<S, B> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    #[pin]
+    gate: Option<B>,
+    pending: Option<S::Item>,
+    first: bool,
+}
+
+impl<S, B> DelayUntil<S, B> where S: Signal, B: Signal<Item = bool> {
+    fn poll_gate(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<S::Item>> {
+        let DelayUntilProj { mut signal, mut gate, pending, .. } = self.project();
+
+        loop {
+            match gate.as_mut().as_pin_mut().map(|gate| gate.poll_change(cx)) {
+                Some(Poll::Ready(Some(true))) => {
+                    // The gate is opened permanently, so it is no longer needed
+                    gate.set(None);
+
+                    if let Some(value) = pending.take() {
+                        return Poll::Ready(Some(value));
+                    }
+
+                    break;
+                },
+                Some(Poll::Ready(Some(false))) => {
+                    continue;
+                },
+                Some(Poll::Ready(None)) => {
+                    return Poll::Ready(None);
+                },
+                Some(Poll::Pending) => {
+                    loop {
+                        match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                            Some(Poll::Ready(Some(value))) => {
+                                *pending = Some(value);
+                            },
+                            Some(Poll::Ready(None)) => {
+                                signal.set(None);
+                                break;
+                            },
+                            Some(Poll::Pending) | None => {
+                                break;
+                            },
+                        }
+                    }
+
+                    return Poll::Pending;
+                },
+                None => {
+                    break;
+                },
+            }
+        }
+
+        match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+            Some(Poll::Ready(None)) | None => {
+                signal.set(None);
+                Poll::Ready(None)
+            },
+            Some(poll) => poll,
+        }
+    }
+}
+
+impl<S, B> Signal for DelayUntil<S, B> where S: Signal, B: Signal<Item = bool> {
+    type Item = Option<S::Item>;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("DelayUntil", {
+            let poll = self.as_mut().poll_gate(cx);
+            poll_first(self.project().first, poll)
+        })
+    }
+}
+
+
+#[pin_project(project = MovingAverageProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MovingAverage<S> {
+    #[pin]
+    signal: S,
+    window: usize,
+    values: VecDeque<f64>,
+}
+
+impl<S> Signal for MovingAverage<S> where S: Signal<Item = f64> {
+    type Item = f64;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("MovingAverage", {
+            let MovingAverageProj { signal, window, values } = self.project();
+
+            signal.poll_change(cx).map(|value| value.map(|value| {
+                if values.len() == *window {
+                    values.pop_front();
+                }
+
+                values.push_back(value);
+
+                values.iter().sum::<f64>() / (values.len() as f64)
+            }))
+        })
+    }
+}
+
+
+/// This is returned by the [`SignalExtra::to_future_timeout`] method.
+#[pin_project(project = SignalFutureTimeoutProj)]
+#[derive(Debug)]
+#[must_use = "Futures do nothing unless polled"]
+pub struct SignalFutureTimeout<S> {
+    #[pin]
+    signal: Option<S>,
+    delay: Option<Delay>,
+}
+
+impl<S> Future for SignalFutureTimeout<S> where S: Signal {
+    type Output = Option<S::Item>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let SignalFutureTimeoutProj { mut signal, delay } = self.project();
+
+        let poll = match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+            Some(Poll::Ready(value)) => Poll::Ready(value),
+            Some(Poll::Pending) => match delay.as_mut().map(|delay| delay.poll_delay(cx)) {
+                Some(Poll::Ready(())) | None => Poll::Ready(None),
+                Some(Poll::Pending) => Poll::Pending,
+            },
+            None => Poll::Ready(None),
+        };
+
+        if poll.is_ready() {
+            signal.set(None);
+            *delay = None;
+        }
+
+        poll
+    }
+}
+
+
+#[pin_project(project = TimestampedProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Timestamped<S> {
+    #[pin]
+    signal: S,
+}
+
+impl<S> Signal for Timestamped<S> where S: Signal {
+    type Item = (f64, S::Item);
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Timestamped", {
+            self.project().signal.poll_change(cx).map(|value| value.map(|value| (bindings::performance_now(), value)))
+        })
+    }
+}
+
+
+#[pin_project(project = DeltaProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Delta<S> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    previous: Option<S::Item>,
+    first: bool,
+}
+
+impl<S> Signal for Delta<S> where S: Signal, S::Item: std::ops::Sub + Copy {
+    type Item = Option<<S::Item as std::ops::Sub>::Output>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Delta", {
+            let DeltaProj { mut signal, previous, first } = self.project();
+
+            let poll = loop {
+                break match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        match previous.replace(value) {
+                            Some(previous) => Poll::Ready(Some(value - previous)),
+                            None => continue,
+                        }
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        Poll::Ready(None)
+                    },
+                    Some(Poll::Pending) => Poll::Pending,
+                    None => Poll::Ready(None),
+                };
+            };
+
+            poll_first(first, poll)
+        })
+    }
+}
+
+
+#[pin_project(project = EmaProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Ema<S> {
+    #[pin]
+    signal: S,
+    alpha: f64,
+    previous: Option<f64>,
+}
+
+impl<S> Signal for Ema<S> where S: Signal<Item = f64> {
+    type Item = f64;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Ema", {
+            let EmaProj { signal, alpha, previous } = self.project();
+
+            signal.poll_change(cx).map(|value| value.map(|value| {
+                let value = match *previous {
+                    Some(previous) => *alpha * value + (1.0 - *alpha) * previous,
+                    None => value,
+                };
+
+                *previous = Some(value);
+                value
+            }))
+        })
+    }
+}
+
+
+#[pin_project(project = StopAfterProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct StopAfter<S> {
+    #[pin]
+    signal: Option<S>,
+    delay: Option<Delay>,
+}
+
+impl<S> Signal for StopAfter<S> where S: Signal {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("StopAfter", {
+            let StopAfterProj { mut signal, delay } = self.project();
+
+            if let Some(Poll::Ready(())) = delay.as_mut().map(|delay| delay.poll_delay(cx)) {
+                signal.set(None);
+                *delay = None;
+            }
+
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                Some(Poll::Ready(Some(value))) => Poll::Ready(Some(value)),
+                Some(Poll::Pending) => Poll::Pending,
+                Some(Poll::Ready(None)) | None => {
+                    signal.set(None);
+                    *delay = None;
+                    Poll::Ready(None)
+                },
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = PluckProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Pluck<S, B, F> {
+    #[pin]
+    signal: Option<S>,
+    callback: F,
+    previous: Option<B>,
+}
+
+impl<S, B, F> Signal for Pluck<S, B, F>
+    where S: Signal,
+          B: PartialEq + Clone,
+          F: FnMut(&S::Item) -> B {
+    type Item = B;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Pluck", {
+            let PluckProj { mut signal, callback, previous } = self.project();
+
+            loop {
+                return match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        let value = callback(&value);
+
+                        if previous.as_ref() == Some(&value) {
+                            continue;
+                        }
+
+                        *previous = Some(value.clone());
+                        Poll::Ready(Some(value))
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        Poll::Ready(None)
+                    },
+                    Some(Poll::Pending) => Poll::Pending,
+                    None => Poll::Ready(None),
+                };
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = FlattenWithProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct FlattenWith<S, F> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    #[pin]
+    inner: Option<S::Item>,
+    on_switch: F,
+}
+
+impl<S, F> Signal for FlattenWith<S, F>
+    where S: Signal,
+          S::Item: Signal,
+          F: FnMut(&mut S::Item) {
+    type Item = <S::Item as Signal>::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("FlattenWith", {
+            let FlattenWithProj { mut signal, mut inner, on_switch } = self.project();
+
+            let done = match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                None => true,
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                    true
+                },
+                Some(Poll::Ready(Some(mut new_inner))) => {
+                    on_switch(&mut new_inner);
+                    inner.set(Some(new_inner));
+                    false
+                },
+                Some(Poll::Pending) => false,
+            };
+
+            match inner.as_mut().as_pin_mut().map(|inner| inner.poll_change(cx)) {
+                Some(Poll::Ready(None)) => {
+                    inner.set(None);
+
+                    if done {
+                        Poll::Ready(None)
+
+                    } else {
+                        Poll::Pending
+                    }
+                },
+                Some(Poll::Ready(Some(value))) => Poll::Ready(Some(value)),
+                Some(Poll::Pending) => Poll::Pending,
+                None => {
+                    if done {
+                        Poll::Ready(None)
+
+                    } else {
+                        Poll::Pending
+                    }
+                },
+            }
+        })
+    }
+}
+
+
+/// Creates a `Signal` which counts down from `seconds` to `0`.
+///
+/// It outputs `seconds` immediately, and then it outputs the remaining seconds once
+/// per second. After it outputs `0` it ends.
+///
+/// The remaining seconds are calculated from the time when it was first polled, so
+/// it stays accurate even if the browser delays the timer (e.g. in background tabs).
+///
+/// The timer is cancelled when the `Signal` is dropped.
+#[inline]
+pub fn countdown(seconds: u32) -> Countdown {
+    Countdown {
+        seconds,
+        remaining: seconds,
+        start: None,
+        ticker: None,
+    }
+}
+
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Countdown {
+    seconds: u32,
+    remaining: u32,
+    start: Option<f64>,
+    ticker: Option<Ticker>,
+}
+
+impl Signal for Countdown {
+    type Item = u32;
+
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Countdown", {
+            let this = &mut *self;
+
+            let start = match this.start {
+                Some(start) => start,
+                None => {
+                    this.start = Some(bindings::performance_now());
+
+                    if this.remaining > 0 {
+                        let mut ticker = Ticker::new(1000);
+
+                        // Starts the timer
+                        let _ = ticker.poll_tick(cx);
+
+                        this.ticker = Some(ticker);
+                    }
+
+                    return Poll::Ready(Some(this.remaining));
+                },
+            };
+
+            match &mut this.ticker {
+                Some(ticker) => {
+                    while ticker.poll_tick(cx).is_ready() {
+                        // Rounded so that a tick which fires slightly early still counts
+                        let elapsed = ((bindings::performance_now() - start) / 1000.0).round() as u32;
+                        let remaining = this.seconds.saturating_sub(elapsed);
+
+                        if remaining != this.remaining {
+                            this.remaining = remaining;
+
+                            if remaining == 0 {
+                                this.ticker = None;
+                            }
+
+                            return Poll::Ready(Some(remaining));
+                        }
+                    }
+
+                    Poll::Pending
+                },
+                None => Poll::Ready(None),
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = MapCachedProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapCached<S, B, F> where S: Signal {
+    #[pin]
+    signal: S,
+    callback: F,
+    cached: Option<(S::Item, B)>,
+}
+
+impl<S, B, F> Signal for MapCached<S, B, F>
+    where S: Signal,
+          S::Item: PartialEq,
+          B: Clone,
+          F: FnMut(&S::Item) -> B {
+    type Item = B;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("MapCached", {
+            let MapCachedProj { signal, callback, cached } = self.project();
+
+            signal.poll_change(cx).map(|value| value.map(|value| {
+                match cached {
+                    Some((input, output)) if *input == value => output.clone(),
+                    _ => {
+                        let output = callback(&value);
+                        *cached = Some((value, output.clone()));
+                        output
+                    },
+                }
+            }))
+        })
+    }
+}
+
+
+#[pin_project(project = IndexByProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct IndexBy<S, F> {
+    #[pin]
+    signal: S,
+    key: F,
+}
+
+impl<A, K, S, F> Signal for IndexBy<S, F>
+    where S: Signal<Item = Vec<A>>,
+          K: Hash + Eq,
+          F: FnMut(&A) -> K {
+    type Item = HashMap<K, A>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("IndexBy", {
+            let IndexByProj { signal, key } = self.project();
+
+            signal.poll_change(cx).map(|values| values.map(|values| {
+                values.into_iter().map(|value| (key(&value), value)).collect()
+            }))
+        })
+    }
+}
+
+
+#[pin_project(project = OnFirstProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct OnFirst<S, F> {
+    #[pin]
+    signal: S,
+    callback: Option<F>,
+}
+
+impl<S, F> Signal for OnFirst<S, F>
+    where S: Signal,
+          F: FnOnce(&S::Item) {
+    type Item = S::Item;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("OnFirst", {
+            let OnFirstProj { signal, callback } = self.project();
+
+            let poll = signal.poll_change(cx);
+
+            if let Poll::Ready(Some(value)) = &poll {
+                if let Some(callback) = callback.take() {
+                    callback(value);
+                }
+            }
+
+            poll
+        })
+    }
+}
+
+
+#[pin_project(project = Map2DedupeProj)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Map2Dedupe<A, B, C, F> where A: Signal, B: Signal {
+    #[pin]
+    signal: Map2<A, B, F>,
+    previous: Option<C>,
+}
+
+impl<A, B, C, F> fmt::Debug for Map2Dedupe<A, B, C, F>
+    where A: Signal + fmt::Debug,
+          B: Signal + fmt::Debug,
+          A::Item: fmt::Debug,
+          B::Item: fmt::Debug,
+          C: fmt::Debug,
+          F: fmt::Debug {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Map2Dedupe")
+            .field("signal", &self.signal)
+            .field("previous", &self.previous)
+            .finish()
+    }
+}
+
+impl<A, B, C, F> Signal for Map2Dedupe<A, B, C, F>
+    where A: Signal,
+          B: Signal,
+          C: PartialEq + Clone,
+          F: FnMut(&A::Item, &B::Item) -> C {
+    type Item = C;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Map2Dedupe", {
+            let Map2DedupeProj { mut signal, previous } = self.project();
+
+            loop {
+                return match signal.as_mut().poll_change(cx) {
+                    Poll::Ready(Some(value)) => {
+                        if previous.as_ref() == Some(&value) {
+                            continue;
+                        }
+
+                        *previous = Some(value.clone());
+                        Poll::Ready(Some(value))
+                    },
+                    Poll::Ready(None) => Poll::Ready(None),
+                    Poll::Pending => Poll::Pending,
+                };
+            }
+        })
+    }
+}
+
+
+#[derive(Debug)]
+struct FinallyCallback<F>(Option<F>) where F: FnOnce();
+
+impl<F> FinallyCallback<F> where F: FnOnce() {
+    #[inline]
+    fn call(&mut self) {
+        if let Some(callback) = self.0.take() {
+            callback();
+        }
+    }
+}
+
+impl<F> Drop for FinallyCallback<F> where F: FnOnce() {
+    #[inline]
+    fn drop(&mut self) {
+        self.call();
+    }
+}
+
+#[pin_project(project = FinallyProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Finally<S, F> where F: FnOnce() {
+    #[pin]
+    signal: S,
+    callback: FinallyCallback<F>,
+}
+
+impl<S, F> Signal for Finally<S, F>
+    where S: Signal,
+          F: FnOnce() {
+    type Item = S::Item;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Finally", {
+            let FinallyProj { signal, callback } = self.project();
+
+            let poll = signal.poll_change(cx);
+
+            if let Poll::Ready(None) = poll {
+                callback.call();
+            }
+
+            poll
+        })
+    }
+}
+
+
+#[pin_project(project = MapWhileProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapWhile<S, F> {
+    #[pin]
+    signal: Option<S>,
+    callback: F,
+}
+
+impl<B, S, F> Signal for MapWhile<S, F>
+    where S: Signal,
+          F: FnMut(S::Item) -> Option<B> {
+    type Item = B;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("MapWhile", {
+            let MapWhileProj { mut signal, callback } = self.project();
+
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                Some(Poll::Ready(Some(value))) => {
+                    let value = callback(value);
+
+                    if value.is_none() {
+                        signal.set(None);
+                    }
+
+                    Poll::Ready(value)
+                },
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                    Poll::Ready(None)
+                },
+                Some(Poll::Pending) => Poll::Pending,
+                None => Poll::Ready(None),
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = BufferUntilProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct BufferUntil<S, B> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    #[pin]
+    flush: Option<B>,
+    emit_empty: bool,
+    buffer: Vec<S::Item>,
+}
+
+impl<S, B> Signal for BufferUntil<S, B> where S: Signal, B: Signal {
+    type Item = Vec<S::Item>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("BufferUntil", {
+            let BufferUntilProj { mut signal, mut flush, emit_empty, buffer } = self.project();
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        buffer.push(value);
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            let mut flushed = false;
+
+            loop {
+                match flush.as_mut().as_pin_mut().map(|flush| flush.poll_change(cx)) {
+                    Some(Poll::Ready(Some(_))) => {
+                        flushed = true;
+                    },
+                    Some(Poll::Ready(None)) => {
+                        flush.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            if flushed && (*emit_empty || !buffer.is_empty()) {
+                Poll::Ready(Some(std::mem::take(buffer)))
+
+            } else if flush.is_none() {
+                if buffer.is_empty() {
+                    Poll::Ready(None)
+
+                } else {
+                    Poll::Ready(Some(std::mem::take(buffer)))
+                }
+
+            } else if signal.is_none() && buffer.is_empty() {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = SwitchMapDedupeProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct SwitchMapDedupe<S, K, A, F, G> {
+    #[pin]
+    signal: Option<S>,
+    #[pin]
+    inner: Option<A>,
+    current_key: Option<K>,
+    key: F,
+    make: G,
+}
+
+impl<S, K, A, F, G> Signal for SwitchMapDedupe<S, K, A, F, G>
+    where S: Signal,
+          K: PartialEq,
+          A: Signal,
+          F: FnMut(&S::Item) -> K,
+          G: FnMut(S::Item) -> A {
+    type Item = A::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("SwitchMapDedupe", {
+            let SwitchMapDedupeProj { mut signal, mut inner, current_key, key, make } = self.project();
+
+            let done = loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        let new_key = key(&value);
+
+                        if current_key.as_ref() != Some(&new_key) {
+                            *current_key = Some(new_key);
+                            inner.set(Some(make(value)));
+                        }
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break true;
+                    },
+                    Some(Poll::Pending) => {
+                        break false;
+                    },
+                    None => {
+                        break true;
+                    },
+                }
+            };
+
+            match inner.as_mut().as_pin_mut().map(|inner| inner.poll_change(cx)) {
+                Some(Poll::Ready(Some(value))) => Poll::Ready(Some(value)),
+                Some(Poll::Ready(None)) => {
+                    inner.set(None);
+
+                    if done {
+                        Poll::Ready(None)
+
+                    } else {
+                        Poll::Pending
+                    }
+                },
+                Some(Poll::Pending) => Poll::Pending,
+                None => {
+                    if done {
+                        Poll::Ready(None)
+
+                    } else {
+                        Poll::Pending
+                    }
+                },
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = InspectNotChangedProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct InspectNotChanged<S, F> {
+    #[pin]
+    signal: S,
+    callback: F,
+}
+
+impl<S, F> Signal for InspectNotChanged<S, F> where S: Signal, F: FnMut() {
+    type Item = S::Item;
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("InspectNotChanged", {
+            let InspectNotChangedProj { signal, callback } = self.project();
+
+            let poll = signal.poll_change(cx);
+
+            if poll.is_pending() {
+                callback();
+            }
+
+            poll
+        })
+    }
+}
+
+
+#[pin_project(project = RepeatEveryProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct RepeatEvery<S> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    ticker: Ticker,
+    value: Option<S::Item>,
+}
+
+impl<S> Signal for RepeatEvery<S> where S: Signal, S::Item: Clone {
+    type Item = S::Item;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("RepeatEvery", {
+            let RepeatEveryProj { mut signal, ticker, value } = self.project();
+
+            let mut changed = false;
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(new_value))) => {
+                        *value = Some(new_value);
+                        changed = true;
+                    },
+                    Some(Poll::Ready(None)) | None => {
+                        signal.set(None);
+
+                        // Outputs the final value before ending
+                        return if changed {
+                            Poll::Ready(value.clone())
+
+                        } else {
+                            Poll::Ready(None)
+                        };
+                    },
+                    Some(Poll::Pending) => {
+                        break;
+                    },
+                }
+            }
+
+            // Changes are outputted before the timer is polled, so the first value is outputted immediately
+            if changed {
+                return Poll::Ready(value.clone());
+            }
+
+            loop {
+                if ticker.poll_tick(cx).is_pending() {
+                    return Poll::Pending;
+                }
+
+                if value.is_some() {
+                    return Poll::Ready(value.clone());
+                }
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = MapThrottledProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapThrottled<S, F> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    delay: Option<Delay>,
+    ms: u32,
+    pending: Option<S::Item>,
+    callback: F,
+}
+
+impl<B, S, F> Signal for MapThrottled<S, F>
+    where S: Signal,
+          F: FnMut(S::Item) -> B {
+    type Item = B;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("MapThrottled", {
+            let MapThrottledProj { mut signal, delay, ms, pending, callback } = self.project();
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        *pending = Some(value);
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            if let Some(timer) = delay {
+                if timer.poll_delay(cx).is_ready() {
+                    *delay = None;
+
+                } else {
+                    return Poll::Pending;
+                }
+            }
+
+            if let Some(value) = pending.take() {
+                let mut timer = Delay::new(*ms);
+
+                // Starts the timer
+                let _ = timer.poll_delay(cx);
+
+                *delay = Some(timer);
+
+                Poll::Ready(Some(callback(value)))
+
+            } else if signal.is_none() {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+}
+
+
+type Sample<A, B> = fn(A, &B) -> (A, B);
+
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct SampleSignal<S, B> where S: Signal, B: Signal {
+    #[pin]
+    signal: WithLatestFrom<S, B, Sample<S::Item, B::Item>>,
+}
+
+impl<S, B> Signal for SampleSignal<S, B> where S: Signal, B: Signal, B::Item: Clone {
+    type Item = (S::Item, B::Item);
+
+    #[inline]
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("SampleSignal", {
+            self.project().signal.poll_change(cx)
+        })
+    }
+}
+
+
+#[pin_project(project = ThrottleAndCollectProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct ThrottleAndCollect<S> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    delay: Option<Delay>,
+    ms: u32,
+    buffer: Vec<S::Item>,
+}
+
+impl<S> Signal for ThrottleAndCollect<S> where S: Signal {
+    type Item = Vec<S::Item>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("ThrottleAndCollect", {
+            let ThrottleAndCollectProj { mut signal, delay, ms, buffer } = self.project();
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        buffer.push(value);
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            if let Some(timer) = delay {
+                if timer.poll_delay(cx).is_ready() {
+                    *delay = None;
+
+                } else {
+                    return Poll::Pending;
+                }
+            }
+
+            if !buffer.is_empty() {
+                let mut timer = Delay::new(*ms);
+
+                // Starts the timer
+                let _ = timer.poll_delay(cx);
+
+                *delay = Some(timer);
+
+                Poll::Ready(Some(std::mem::take(buffer)))
+
+            } else if signal.is_none() {
+                Poll::Ready(None)
+
+            } else {
+                Poll::Pending
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = MapWeakProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct MapWeak<S, A, F> {
+    #[pin]
+    signal: S,
+    target: Weak<A>,
+    callback: F,
+}
+
+impl<S, A, B, F> Signal for MapWeak<S, A, F>
+    where S: Signal,
+          F: FnMut(&A, S::Item) -> B {
+    type Item = B;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("MapWeak", {
+            let MapWeakProj { mut signal, target, callback } = self.project();
+
+            loop {
+                return match signal.as_mut().poll_change(cx) {
+                    Poll::Ready(Some(value)) => match target.upgrade() {
+                        Some(target) => Poll::Ready(Some(callback(&target, value))),
+                        None => continue,
+                    },
+                    Poll::Ready(None) => Poll::Ready(None),
+                    Poll::Pending => Poll::Pending,
+                };
+            }
+        })
+    }
+}
+
+
+/// This is returned by the [`SignalExtra::first_n`] method.
+#[pin_project(project = FirstNProj)]
+#[derive(Debug)]
+#[must_use = "Futures do nothing unless polled"]
+pub struct FirstN<S> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    n: usize,
+    values: Vec<S::Item>,
+}
+
+impl<S> Future for FirstN<S> where S: Signal {
+    type Output = Vec<S::Item>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let FirstNProj { mut signal, n, values } = self.project();
+
+        while values.len() < *n {
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                Some(Poll::Ready(Some(value))) => {
+                    values.push(value);
+                },
+                Some(Poll::Ready(None)) | None => {
+                    break;
+                },
+                Some(Poll::Pending) => {
+                    return Poll::Pending;
+                },
+            }
+        }
+
+        signal.set(None);
+        Poll::Ready(std::mem::take(values))
+    }
+}
+
+
+#[pin_project(project = Map2OptionProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Map2Option<A, B, F> where A: Signal, B: Signal {
+    #[pin]
+    left: Option<A>,
+    #[pin]
+    right: Option<B>,
+    left_value: Option<A::Item>,
+    right_value: Option<B::Item>,
+    callback: F,
+}
+
+impl<A, B, C, D, E, F> Signal for Map2Option<A, B, F>
+    where A: Signal<Item = Option<C>>,
+          B: Signal<Item = Option<D>>,
+          F: FnMut(&C, &D) -> E {
+    type Item = Option<E>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Map2Option", {
+            let Map2OptionProj { left, right, left_value, right_value, callback } = self.project();
+
+            poll_map2(cx, left, right, left_value, right_value).map(|changed| {
+                changed.map(|()| {
+                    match (left_value, right_value) {
+                        (Some(Some(left)), Some(Some(right))) => Some(callback(left, right)),
+                        _ => None,
+                    }
+                })
+            })
+        })
+    }
+}
+
+
+#[pin_project(project = RetryWithBackoffProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct RetryWithBackoff<S, Fut> where S: Signal {
+    #[pin]
+    signal: Option<S>,
+    make: Option<S::Item>,
+    future: Option<Pin<Box<Fut>>>,
+    delay: Option<Delay>,
+    attempt: usize,
+    max: usize,
+    base_ms: u32,
+}
+
+impl<A, E, S, F, Fut> Signal for RetryWithBackoff<S, Fut>
+    where S: Signal<Item = F>,
+          F: FnMut() -> Fut,
+          Fut: Future<Output = Result<A, E>> {
+    type Item = Result<A, E>;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("RetryWithBackoff", {
+            let RetryWithBackoffProj { mut signal, make, future, delay, attempt, max, base_ms } = self.project();
+
+            loop {
+                match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(mut new_make))) => {
+                        *future = Some(Box::pin(new_make()));
+                        *make = Some(new_make);
+                        *delay = None;
+                        *attempt = 0;
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            loop {
+                if let Some(timer) = delay {
+                    if timer.poll_delay(cx).is_ready() {
+                        *delay = None;
+
+                        if let Some(make) = make {
+                            *future = Some(Box::pin(make()));
+                        }
+
+                    } else {
+                        return Poll::Pending;
+                    }
+                }
+
+                match future.as_mut().map(|future| future.as_mut().poll(cx)) {
+                    Some(Poll::Ready(Ok(value))) => {
+                        *future = None;
+                        *attempt = 0;
+                        return Poll::Ready(Some(Ok(value)));
+                    },
+                    Some(Poll::Ready(Err(error))) => {
+                        *future = None;
+                        *attempt += 1;
+
+                        if *attempt >= *max {
+                            *attempt = 0;
+                            return Poll::Ready(Some(Err(error)));
+
+                        } else {
+                            let backoff = 2u32.saturating_pow((*attempt - 1) as u32);
+                            *delay = Some(Delay::new(base_ms.saturating_mul(backoff)));
+                        }
+                    },
+                    Some(Poll::Pending) => {
+                        return Poll::Pending;
+                    },
+                    None => {
+                        return if signal.is_none() {
+                            Poll::Ready(None)
+
+                        } else {
+                            Poll::Pending
+                        };
+                    },
+                }
+            }
+        })
+    }
+}
+
+
+/// Outputs `true` when the input signal changes from `false` to `true`, and `false`
+/// when it changes from `true` to `false`.
+#[pin_project(project = EdgesProj)]
+#[derive(Debug)]
+struct Edges<S> {
+    #[pin]
+    signal: S,
+    previous: Option<bool>,
+}
+
+impl<S> Signal for Edges<S> where S: Signal<Item = bool> {
+    type Item = bool;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Edges", {
+            let EdgesProj { mut signal, previous } = self.project();
+
+            loop {
+                match signal.as_mut().poll_change(cx) {
+                    Poll::Ready(Some(value)) => {
+                        if let Some(old) = previous.replace(value) {
+                            if old != value {
+                                return Poll::Ready(Some(value));
+                            }
+                        }
+                    },
+                    Poll::Ready(None) => return Poll::Ready(None),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        })
+    }
+}
+
+/// This is returned by the [`SignalExtra::split_edges`] method.
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct Edge<S> {
+    split: SplitHalf<Edges<S>, ()>,
+    first: bool,
+}
+
+impl<S> Unpin for Edge<S> {}
+
+impl<S> Signal for Edge<S> where S: Signal<Item = bool> {
+    type Item = Option<()>;
+
+    #[inline]
+    fn poll_change(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("Edge", {
+            let this = &mut *self;
+
+            // Index 0 is rising, index 1 is falling
+            let poll = this.split.poll(cx, |rising, values| {
+                values[if rising { 0 } else { 1 }] = Some(());
+            });
+
+            poll_first(&mut this.first, poll)
+        })
+    }
+}
+
+
+#[pin_project(project = WithLatestFromProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct WithLatestFrom<S, B, F> where S: Signal, B: Signal {
+    #[pin]
+    signal: Option<S>,
+    #[pin]
+    other: Option<B>,
+    pending: Option<S::Item>,
+    other_value: Option<B::Item>,
+    callback: F,
+}
+
+impl<S, B, C, F> Signal for WithLatestFrom<S, B, F>
+    where S: Signal,
+          B: Signal,
+          F: FnMut(S::Item, &B::Item) -> C {
+    type Item = C;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("WithLatestFrom", {
+            let WithLatestFromProj { mut signal, mut other, pending, other_value, callback } = self.project();
+
+            loop {
+                match other.as_mut().as_pin_mut().map(|other| other.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        *other_value = Some(value);
+                    },
+                    Some(Poll::Ready(None)) => {
+                        other.set(None);
+                        break;
+                    },
+                    Some(Poll::Pending) | None => {
+                        break;
+                    },
+                }
+            }
+
+            match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                Some(Poll::Ready(Some(value))) => {
+                    *pending = Some(value);
+                },
+                Some(Poll::Ready(None)) => {
+                    signal.set(None);
+                },
+                Some(Poll::Pending) | None => {},
+            }
+
+            match other_value {
+                Some(other_value) if pending.is_some() => {
+                    Poll::Ready(pending.take().map(|value| callback(value, other_value)))
+                },
+                _ => {
+                    if (signal.is_none() && pending.is_none()) || (other.is_none() && other_value.is_none()) {
+                        Poll::Ready(None)
+
+                    } else {
+                        Poll::Pending
+                    }
+                },
+            }
+        })
+    }
+}
+
+
+#[pin_project(project = DedupeEpsilonProj)]
+#[derive(Debug)]
+#[must_use = "Signals do nothing unless polled"]
+pub struct DedupeEpsilon<S> {
+    #[pin]
+    signal: Option<S>,
+    epsilon: f64,
+    previous: Option<f64>,
+}
+
+impl<S> Signal for DedupeEpsilon<S> where S: Signal<Item = f64> {
+    type Item = f64;
+
+    fn poll_change(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+        trace_poll!("DedupeEpsilon", {
+            let DedupeEpsilonProj { mut signal, epsilon, previous } = self.project();
+
+            loop {
+                return match signal.as_mut().as_pin_mut().map(|signal| signal.poll_change(cx)) {
+                    Some(Poll::Ready(Some(value))) => {
+                        if let Some(previous) = previous {
+                            if (value - *previous).abs() < *epsilon {
+                                continue;
+                            }
+                        }
+
+                        *previous = Some(value);
+                        Poll::Ready(Some(value))
+                    },
+                    Some(Poll::Ready(None)) => {
+                        signal.set(None);
+                        Poll::Ready(None)
+                    },
+                    Some(Poll::Pending) => Poll::Pending,
+                    None => Poll::Ready(None),
+                };
+            }
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+    use std::collections::HashMap;
+    use std::cell::Cell;
+    use std::task::{Context, Poll};
+    use futures_signals::signal::{Signal, SignalExt, Mutable, always};
+    use futures_signals::cancelable_future;
+    use futures_util::future::{pending, ready, FutureExt};
+    use futures_util::task::noop_waker_ref;
+    use super::{SignalExtra, SignalCell, Change, DropHandle};
+
+    fn poll<S>(signal: &mut S) -> Poll<Option<S::Item>> where S: Signal + Unpin {
+        signal.poll_change_unpin(&mut Context::from_waker(noop_waker_ref()))
+    }
+
+    #[test]
+    fn signal_cell() {
+        let cell = SignalCell::new(1);
+        let mut signal = cell.signal();
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+
+        assert_eq!(*cell.borrow_mut(), 1);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        *cell.borrow_mut() = 2;
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+
+        drop(cell);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn map_index() {
+        let calls = Rc::new(Cell::new(0));
+        let input = Mutable::new(vec![1, 2, 3]);
+
+        let mut signal = input.signal_cloned().map_index(|x| *x, {
+            let calls = calls.clone();
+            move |x| {
+                calls.set(calls.get() + 1);
+                x * 10
+            }
+        });
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(vec![10, 20, 30])));
+        assert_eq!(calls.get(), 3);
+
+        input.set(vec![3, 4, 1]);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(vec![30, 40, 10])));
+        assert_eq!(calls.get(), 4);
+
+        input.set(vec![2]);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(vec![20])));
+        assert_eq!(calls.get(), 5);
+    }
+
+    #[test]
+    fn unwrap_or_else() {
+        let input = Mutable::new(Ok(1));
+        let mut signal = input.signal_cloned().unwrap_or_else(|error: String| error.len());
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+
+        input.set(Err("error".to_string()));
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(5)));
+
+        input.set(Ok(2));
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+    }
+
+    #[test]
+    fn race() {
+        let left = Mutable::new(1);
+        let right = Mutable::new(2);
+
+        let mut signal = left.signal().race(right.signal());
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+
+        right.set(3);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        left.set(4);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(4)));
+
+        drop(left);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn throttle_frames() {
+        let input = Mutable::new(1);
+        let mut signal = input.signal().throttle_frames();
+
+        // The first value doesn't wait for an animation frame
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+    }
+
+    #[test]
+    fn changes() {
+        let input = Mutable::new(1);
+        let mut signal = input.signal().changes();
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Change { old: None, new: 1 })));
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Change { old: Some(1), new: 2 })));
+
+        input.set(3);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Change { old: Some(2), new: 3 })));
+
+        drop(input);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn drop_handle_stop_all() {
+        let mut futures = vec![];
+
+        let handles = (0..3).map(|_| {
+            let (handle, future) = cancelable_future(pending::<()>(), || ());
+            futures.push(future);
+            DropHandle { _handle: handle }
+        }).collect::<Vec<_>>();
+
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        for future in futures.iter_mut() {
+            assert_eq!(future.poll_unpin(&mut cx), Poll::Pending);
+        }
+
+        DropHandle::stop_all(handles);
+
+        for future in futures.iter_mut() {
+            assert_eq!(future.poll_unpin(&mut cx), Poll::Ready(()));
+        }
+    }
+
+    // Outputs nothing until `open` is `true`, this is used to test signals which don't change yet
+    struct Closed<S> {
+        signal: S,
+        open: Rc<Cell<bool>>,
+    }
+
+    impl<S> Signal for Closed<S> where S: Signal + Unpin {
+        type Item = S::Item;
+
+        fn poll_change(mut self: std::pin::Pin<&mut Self>, cx: &mut Context) -> Poll<Option<Self::Item>> {
+            if self.open.get() {
+                self.signal.poll_change_unpin(cx)
+
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn or() {
+        let input = Mutable::new(1);
+        let gate = Rc::new(Cell::new(false));
+        let fallback = Mutable::new(0);
+        let mut signal = Closed { signal: input.signal(), open: gate.clone() }.or(fallback.signal());
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(0)));
+
+        fallback.set(5);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(5)));
+
+        gate.set(true);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+
+        fallback.set(6);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+
+        drop(input);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn sample_each() {
+        let input = Mutable::new(1);
+        let mut signal = input.signal().sample_each(1000);
+
+        // The first value doesn't wait for the timer
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+    }
+
+    #[test]
+    fn group_consecutive() {
+        let input = Mutable::new(1);
+        let mut signal = input.signal().group_consecutive();
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(None)));
+
+        input.set(1);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some((1, 2)))));
+
+        drop(input);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some((2, 1)))));
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn flat_map_vec() {
+        let input = Mutable::new(3);
+        let mut signal = input.signal().flat_map_vec(|x| (0..x).collect());
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(0)));
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        // An empty Vec doesn't output anything
+        input.set(0);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(0)));
+
+        // The buffered values are still outputted after the signal ends
+        drop(input);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn ops() {
+        let price = Mutable::new(5);
+        let quantity = Mutable::new(2);
+
+        let mut signal = (price.signal().ops() * quantity.signal()) + always(1);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(11)));
+
+        quantity.set(3);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(16)));
+        assert_eq!(poll(&mut signal), Poll::Pending);
+    }
+
+    #[test]
+    fn map_option() {
+        let input = Mutable::new(Some(1));
+        let mut signal = input.signal().map_option(|x| x * 2);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(2))));
+
+        input.set(None);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(None)));
+
+        input.set(Some(3));
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(6))));
+    }
+
+    #[test]
+    fn and_then_option() {
+        let input = Mutable::new(Some(4));
+        let mut signal = input.signal().and_then_option(|x| if x % 2 == 0 { Some(x / 2) } else { None });
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(2))));
+
+        // The callback returns None
+        input.set(Some(3));
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(None)));
+
+        input.set(None);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(None)));
+
+        input.set(Some(8));
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(4))));
+    }
+
+    #[test]
+    fn settle() {
+        let input = Mutable::new(1);
+        let mut signal = input.signal().settle(2);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(None)));
+
+        input.set(1);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(1))));
+
+        input.set(1);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(3);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(3);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(3))));
+
+        drop(input);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn from_oneshot() {
+        let (sender, receiver) = futures_channel::oneshot::channel();
+        let mut signal = super::from_oneshot(receiver);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(None)));
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        sender.send(5).unwrap();
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(5))));
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+
+        let (sender, receiver) = futures_channel::oneshot::channel::<u32>();
+        let mut signal = super::from_oneshot(receiver);
+
+        drop(sender);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn split_map() {
+        let polls = Rc::new(Cell::new(0));
+
+        let input = Mutable::new(1);
+
+        let (mut left, mut right) = input.signal()
+            .inspect({
+                let polls = polls.clone();
+                move |_| polls.set(polls.get() + 1)
+            })
+            .split_map(|x| x + 1, |x| format!("{}", x));
+
+        assert_eq!(poll(&mut left), Poll::Ready(Some(2)));
+        assert_eq!(poll(&mut right), Poll::Ready(Some("1".to_string())));
+        assert_eq!(poll(&mut left), Poll::Pending);
+        assert_eq!(poll(&mut right), Poll::Pending);
+
+        input.set(5);
+        assert_eq!(poll(&mut right), Poll::Ready(Some("5".to_string())));
+        assert_eq!(poll(&mut left), Poll::Ready(Some(6)));
+        assert_eq!(polls.get(), 2);
+
+        drop(left);
+        drop(input);
+        assert_eq!(poll(&mut right), Poll::Ready(None));
+    }
+
+    #[test]
+    fn throttle_with() {
+        let input = Mutable::new(1);
+        let opener = Mutable::new(());
+        let mut signal = input.signal().throttle_with(opener.signal());
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+
+        input.set(2);
+        input.set(3);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        opener.set(());
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(3)));
+
+        opener.set(());
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(4);
+        drop(input);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        opener.set(());
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(4)));
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn repeat_last() {
+        let input = Mutable::new(1);
+        let trigger = Mutable::new(());
+        let mut signal = input.signal().repeat_last(trigger.signal());
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        trigger.set(());
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+
+        input.set(2);
+        trigger.set(());
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        drop(input);
+        trigger.set(());
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+
+        drop(trigger);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn version() {
+        let input = Mutable::new("a");
+        let mut signal = input.signal().version();
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(0)));
+
+        input.set("a");
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+
+        input.set("b");
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+        assert_eq!(poll(&mut signal), Poll::Pending);
+    }
+
+    #[test]
+    fn guarded() {
+        let input = Mutable::new(1);
+        let mut signal = input.signal()
+            .map(|x| {
+                if x == 2 {
+                    panic!("invalid value");
+                }
+
+                x
+            })
+            .guarded();
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+
+        input.set(3);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn delay_until() {
+        let input = Mutable::new(1);
+        let gate = Mutable::new(false);
+        let mut signal = input.signal().delay_until(gate.signal());
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(None)));
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        gate.set(true);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(2))));
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        gate.set(false);
+        input.set(3);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(3))));
+    }
+
+    #[test]
+    fn moving_average() {
+        let input = Mutable::new(2.0);
+        let mut signal = input.signal().moving_average(2);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2.0)));
+
+        input.set(4.0);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(3.0)));
+
+        input.set(10.0);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(7.0)));
+
+        let mut signal = input.signal().moving_average(0);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(10.0)));
+
+        input.set(4.0);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(4.0)));
+    }
+
+    #[test]
+    fn delta() {
+        let input = Mutable::new(5);
+        let mut signal = input.signal().delta();
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(None)));
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(8);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(3))));
+
+        input.set(6);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(-2))));
+    }
+
+    #[test]
+    fn zip_into() {
+        #[derive(Debug, PartialEq)]
+        struct Two {
+            name: String,
+            age: u32,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Three {
+            a: u32,
+            b: String,
+            c: u32,
+        }
+
+        #[derive(Debug, PartialEq)]
+        struct Four {
+            a: u32,
+            b: &'static str,
+            c: bool,
+            d: u32,
+        }
+
+        let name = Mutable::new("foo".to_string());
+        let age = Mutable::new(5);
+
+        let mut two = crate::zip_into!(Two {
+            name: name.signal_cloned(),
+            age: age.signal(),
+        });
+
+        assert_eq!(poll(&mut two), Poll::Ready(Some(Two { name: "foo".to_string(), age: 5 })));
+
+        age.set(6);
+        assert_eq!(poll(&mut two), Poll::Ready(Some(Two { name: "foo".to_string(), age: 6 })));
+
+        let mut three = crate::zip_into!(Three {
+            a: age.signal(),
+            b: name.signal_cloned(),
+            c: always(10),
+        });
+
+        assert_eq!(poll(&mut three), Poll::Ready(Some(Three { a: 6, b: "foo".to_string(), c: 10 })));
+
+        name.set("bar".to_string());
+        assert_eq!(poll(&mut three), Poll::Ready(Some(Three { a: 6, b: "bar".to_string(), c: 10 })));
+
+        let mut four = crate::zip_into!(Four {
+            a: always(1),
+            b: always("b"),
+            c: always(true),
+            d: age.signal()
+        });
+
+        assert_eq!(poll(&mut four), Poll::Ready(Some(Four { a: 1, b: "b", c: true, d: 6 })));
+
+        age.set(7);
+        assert_eq!(poll(&mut four), Poll::Ready(Some(Four { a: 1, b: "b", c: true, d: 7 })));
+        assert_eq!(poll(&mut four), Poll::Pending);
+    }
+
+    #[test]
+    fn ema() {
+        let input = Mutable::new(10.0);
+        let mut signal = input.signal().ema(0.5);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(10.0)));
+
+        input.set(20.0);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(15.0)));
+
+        input.set(5.0);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(10.0)));
+
+        // This is the same as an alpha of 1.0
+        let mut signal = input.signal().ema(2.0);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(5.0)));
+
+        input.set(20.0);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(20.0)));
+    }
+
+    #[test]
+    fn pluck() {
+        let input = Mutable::new((1, "a"));
+        let mut signal = input.signal().pluck(|(_, name)| *name);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some("a")));
+
+        input.set((2, "a"));
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set((3, "b"));
+        assert_eq!(poll(&mut signal), Poll::Ready(Some("b")));
+    }
+
+    #[test]
+    fn flatten_with() {
+        let switches = Rc::new(Cell::new(0));
+
+        let first = Mutable::new(1);
+        let second = Mutable::new(10);
+        let outer = Mutable::new(first.clone());
+
+        let mut signal = outer.signal_cloned()
+            .map(|inner| inner.signal())
+            .flatten_with({
+                let switches = switches.clone();
+                move |_| switches.set(switches.get() + 1)
+            });
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+        assert_eq!(switches.get(), 1);
+
+        first.set(2);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+        assert_eq!(switches.get(), 1);
+
+        outer.set(second.clone());
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(10)));
+        assert_eq!(switches.get(), 2);
+
+        first.set(3);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        drop(outer);
+        second.set(11);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(11)));
+        assert_eq!(switches.get(), 2);
+    }
+
+    #[test]
+    fn map_cached() {
+        let calls = Rc::new(Cell::new(0));
+
+        let input = Mutable::new(1);
+
+        let mut signal = input.signal().map_cached({
+            let calls = calls.clone();
+            move |value| {
+                calls.set(calls.get() + 1);
+                value * 10
+            }
+        });
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(10)));
+
+        input.set(1);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(10)));
+        assert_eq!(calls.get(), 1);
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(20)));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn map2_dedupe() {
+        let left = Mutable::new(1);
+        let right = Mutable::new(2);
+        let mut signal = left.signal().map2_dedupe(right.signal(), |left, right| left + right);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(3)));
+
+        left.set(2);
+        right.set(1);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        right.set(5);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(7)));
+
+        drop(left);
+        drop(right);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn index_by() {
+        let input = Mutable::new(vec![(1, "a"), (2, "b"), (1, "c")]);
+        let mut signal = input.signal_cloned().index_by(|(key, _)| *key);
+
+        // The last value with the same key is used
+        let expected: HashMap<_, _> = vec![(1, (1, "c")), (2, (2, "b"))].into_iter().collect();
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(expected)));
+
+        input.set(vec![]);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(HashMap::new())));
+    }
+
+    #[test]
+    fn on_first() {
+        let calls = Rc::new(Cell::new(0));
+
+        let input = Mutable::new(1);
+        let mut signal = input.signal().on_first({
+            let calls = calls.clone();
+            move |x| {
+                assert_eq!(*x, 1);
+                calls.set(calls.get() + 1);
+            }
+        });
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+        assert_eq!(calls.get(), 1);
+
+        assert_eq!(poll(&mut signal), Poll::Pending);
+        assert_eq!(calls.get(), 1);
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+        assert_eq!(calls.get(), 1);
+
+        input.set(3);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(3)));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn finally() {
+        let calls = Rc::new(Cell::new(0));
+
+        let input = Mutable::new(1);
+
+        let mut signal = input.signal().finally({
+            let calls = calls.clone();
+            move || calls.set(calls.get() + 1)
+        });
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+        assert_eq!(calls.get(), 0);
+
+        drop(input);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+        assert_eq!(calls.get(), 1);
+
+        drop(signal);
+        assert_eq!(calls.get(), 1);
+
+        let signal = always(1).finally({
+            let calls = calls.clone();
+            move || calls.set(calls.get() + 1)
+        });
+
+        drop(signal);
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn map_while() {
+        let input = Mutable::new(1);
+        let mut signal = input.signal().map_while(|value| if value < 3 { Some(value * 2) } else { None });
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+
+        input.set(3);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+
+        input.set(1);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn buffer_until() {
+        let input = Mutable::new(1);
+        let flush = Mutable::new(());
+        let mut signal = input.signal().buffer_until(flush.signal(), false);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(vec![1])));
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(3);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        flush.set(());
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(vec![2, 3])));
+
+        flush.set(());
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(4);
+        drop(flush);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(vec![4])));
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn switch_map_dedupe() {
+        let makes = Rc::new(Cell::new(0));
+
+        let input = Mutable::new((1, "a"));
+        let inner = Mutable::new(10);
+
+        let mut signal = input.signal().switch_map_dedupe(|(id, _)| *id, {
+            let makes = makes.clone();
+            let inner = inner.clone();
+            move |(id, _)| {
+                makes.set(makes.get() + 1);
+                inner.signal().map(move |value| value + id)
+            }
+        });
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(11)));
+
+        input.set((1, "b"));
+        assert_eq!(poll(&mut signal), Poll::Pending);
+        assert_eq!(makes.get(), 1);
+
+        input.set((2, "b"));
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(12)));
+        assert_eq!(makes.get(), 2);
+    }
+
+    #[test]
+    fn inspect_not_changed() {
+        let calls = Rc::new(Cell::new(0));
+
+        let input = Mutable::new(1);
+        let mut signal = input.signal().inspect_not_changed({
+            let calls = calls.clone();
+            move || calls.set(calls.get() + 1)
+        });
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+        assert_eq!(calls.get(), 0);
+
+        assert_eq!(poll(&mut signal), Poll::Pending);
+        assert_eq!(calls.get(), 1);
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(2)));
+        assert_eq!(calls.get(), 1);
+
+        assert_eq!(poll(&mut signal), Poll::Pending);
+        assert_eq!(calls.get(), 2);
+
+        drop(input);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+        assert_eq!(calls.get(), 2);
+    }
+
+    #[test]
+    fn repeat_every() {
+        let input = Mutable::new(1);
+        let mut signal = input.signal().repeat_every(1000);
+
+        // The first value doesn't wait for the timer
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1)));
+    }
+
+    #[test]
+    fn sample_signal() {
+        let input = Mutable::new(1);
+        let other = Mutable::new("a");
+        let mut signal = input.signal().sample_signal(other.signal());
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some((1, "a"))));
+
+        other.set("b");
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some((2, "b"))));
+
+        drop(input);
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn map_weak() {
+        let target = Rc::new(10);
+        let input = Mutable::new(1);
+        let mut signal = input.signal().map_weak(&target, |target, value| target + value);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(11)));
+
+        drop(target);
+        input.set(2);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+    }
+
+    #[test]
+    fn first_n() {
+        let mut cx = Context::from_waker(noop_waker_ref());
+
+        let input = Mutable::new(1);
+        let mut future = input.signal().first_n(2);
+
+        assert_eq!(future.poll_unpin(&mut cx), Poll::Pending);
+
+        input.set(2);
+        assert_eq!(future.poll_unpin(&mut cx), Poll::Ready(vec![1, 2]));
+
+        let mut future = always(1).first_n(2);
+        assert_eq!(future.poll_unpin(&mut cx), Poll::Ready(vec![1]));
+    }
+
+    #[test]
+    fn map2_option() {
+        let left = Mutable::new(None);
+        let right = Mutable::new(Some(2));
+        let mut signal = left.signal().map2_option(right.signal(), |left, right| left + right);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(None)));
+
+        left.set(Some(1));
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Some(3))));
+
+        right.set(None);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(None)));
+    }
+
+    #[test]
+    fn retry_with_backoff() {
+        let attempts = Rc::new(Cell::new(0));
+
+        let mut signal = always({
+            let attempts = attempts.clone();
+            move || {
+                attempts.set(attempts.get() + 1);
+                ready(Err::<(), _>("failed"))
+            }
+        }).retry_with_backoff(1, 100);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Err("failed"))));
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+        assert_eq!(attempts.get(), 1);
+
+        let mut signal = always(|| ready(Ok::<_, ()>(5))).retry_with_backoff(3, 100);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(Ok(5))));
+        assert_eq!(poll(&mut signal), Poll::Ready(None));
+    }
+
+    #[test]
+    fn split_edges() {
+        let input = Mutable::new(false);
+        let (mut rising, mut falling) = input.signal().split_edges();
+
+        assert_eq!(poll(&mut rising), Poll::Ready(Some(None)));
+        assert_eq!(poll(&mut falling), Poll::Ready(Some(None)));
+        assert_eq!(poll(&mut rising), Poll::Pending);
+
+        input.set(true);
+        assert_eq!(poll(&mut falling), Poll::Pending);
+        assert_eq!(poll(&mut rising), Poll::Ready(Some(Some(()))));
+
+        input.set(false);
+        assert_eq!(poll(&mut rising), Poll::Pending);
+        assert_eq!(poll(&mut falling), Poll::Ready(Some(Some(()))));
+
+        drop(input);
+        assert_eq!(poll(&mut rising), Poll::Ready(None));
+        assert_eq!(poll(&mut falling), Poll::Ready(None));
+    }
+
+    #[test]
+    fn with_latest_from() {
+        let clicks = Mutable::new(0);
+        let form = Mutable::new("a".to_string());
+        let mut signal = clicks.signal().with_latest_from(form.signal_cloned(), |click, form| format!("{} {}", click, form));
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some("0 a".to_string())));
+
+        form.set("b".to_string());
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        clicks.set(1);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some("1 b".to_string())));
+    }
+
+    #[test]
+    fn dedupe_epsilon() {
+        let input = Mutable::new(1.0);
+        let mut signal = input.signal().dedupe_epsilon(0.5);
+
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1.0)));
+
+        input.set(1.3);
+        assert_eq!(poll(&mut signal), Poll::Pending);
+
+        input.set(1.6);
+        assert_eq!(poll(&mut signal), Poll::Ready(Some(1.6)));
+    }
+}
@@ -0,0 +1,276 @@
+use std::rc::Rc;
+use std::cell::RefCell;
+use std::task::{Poll, Waker, Context};
+
+use wasm_bindgen::JsCast;
+use wasm_bindgen::closure::Closure;
+
+use crate::bindings;
+
+
+// TODO move this into gloo
+struct Timeout {
+    id: i32,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl Timeout {
+    fn new<F>(ms: u32, callback: F) -> Self where F: FnOnce() + 'static {
+        let closure = Closure::once(callback);
+
+        Self {
+            id: bindings::set_timeout(closure.as_ref().unchecked_ref(), ms),
+            _closure: closure,
+        }
+    }
+}
+
+impl Drop for Timeout {
+    #[inline]
+    fn drop(&mut self) {
+        bindings::clear_timeout(self.id);
+    }
+}
+
+
+// TODO move this into gloo
+struct Interval {
+    id: i32,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl Interval {
+    fn new<F>(ms: u32, callback: F) -> Self where F: FnMut() + 'static {
+        let closure = Closure::wrap(Box::new(callback) as Box<dyn FnMut()>);
+
+        Self {
+            id: bindings::set_interval(closure.as_ref().unchecked_ref(), ms),
+            _closure: closure,
+        }
+    }
+}
+
+impl Drop for Interval {
+    #[inline]
+    fn drop(&mut self) {
+        bindings::clear_interval(self.id);
+    }
+}
+
+
+// TODO move this into gloo
+enum IdleCallback {
+    Idle {
+        id: u32,
+        _closure: Closure<dyn FnMut()>,
+    },
+    Timeout {
+        _timeout: Timeout,
+    },
+}
+
+impl IdleCallback {
+    // Used when requestIdleCallback isn't supported
+    const FALLBACK_MS: u32 = 50;
+
+    fn new<F>(callback: F) -> Self where F: FnOnce() + 'static {
+        let callback = Rc::new(RefCell::new(Some(callback)));
+
+        let closure = {
+            let callback = callback.clone();
+
+            Closure::once(move || {
+                if let Some(callback) = callback.borrow_mut().take() {
+                    callback();
+                }
+            })
+        };
+
+        match bindings::request_idle_callback(closure.as_ref().unchecked_ref()) {
+            Some(id) => IdleCallback::Idle { id, _closure: closure },
+            None => IdleCallback::Timeout {
+                _timeout: Timeout::new(Self::FALLBACK_MS, move || {
+                    if let Some(callback) = callback.borrow_mut().take() {
+                        callback();
+                    }
+                }),
+            },
+        }
+    }
+}
+
+impl Drop for IdleCallback {
+    #[inline]
+    fn drop(&mut self) {
+        if let IdleCallback::Idle { id, .. } = self {
+            bindings::cancel_idle_callback(*id);
+        }
+    }
+}
+
+
+#[derive(Debug)]
+struct TimerState {
+    fired: bool,
+    waker: Option<Waker>,
+}
+
+impl TimerState {
+    fn new() -> Rc<RefCell<Self>> {
+        Rc::new(RefCell::new(Self {
+            fired: false,
+            waker: None,
+        }))
+    }
+
+    fn fire(state: &RefCell<Self>) {
+        let waker = {
+            let mut lock = state.borrow_mut();
+            lock.fired = true;
+            lock.waker.take()
+        };
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    fn poll(state: &RefCell<Self>, cx: &mut Context) -> Poll<()> {
+        let mut lock = state.borrow_mut();
+
+        if lock.fired {
+            lock.fired = false;
+            Poll::Ready(())
+
+        } else {
+            lock.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+
+/// Fires once after `ms` milliseconds, the timer is cancelled when this is dropped.
+///
+/// The timer doesn't start until it is polled for the first time.
+pub(crate) struct Delay {
+    ms: u32,
+    state: Rc<RefCell<TimerState>>,
+    timeout: Option<Timeout>,
+}
+
+impl Delay {
+    #[inline]
+    pub(crate) fn new(ms: u32) -> Self {
+        Self {
+            ms,
+            state: TimerState::new(),
+            timeout: None,
+        }
+    }
+
+    pub(crate) fn poll_delay(&mut self, cx: &mut Context) -> Poll<()> {
+        if self.timeout.is_none() {
+            let state = self.state.clone();
+
+            self.timeout = Some(Timeout::new(self.ms, move || {
+                TimerState::fire(&state);
+            }));
+        }
+
+        TimerState::poll(&self.state, cx)
+    }
+}
+
+impl std::fmt::Debug for Delay {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("Delay")
+            .field("ms", &self.ms)
+            .field("started", &self.timeout.is_some())
+            .finish()
+    }
+}
+
+
+/// Fires every `ms` milliseconds, the timer is cancelled when this is dropped.
+///
+/// The timer doesn't start until it is polled for the first time. Multiple
+/// ticks which happen in between polls are merged into a single tick.
+pub(crate) struct Ticker {
+    ms: u32,
+    state: Rc<RefCell<TimerState>>,
+    interval: Option<Interval>,
+}
+
+impl Ticker {
+    #[inline]
+    pub(crate) fn new(ms: u32) -> Self {
+        Self {
+            ms,
+            state: TimerState::new(),
+            interval: None,
+        }
+    }
+
+    pub(crate) fn poll_tick(&mut self, cx: &mut Context) -> Poll<()> {
+        if self.interval.is_none() {
+            let state = self.state.clone();
+
+            self.interval = Some(Interval::new(self.ms, move || {
+                TimerState::fire(&state);
+            }));
+        }
+
+        TimerState::poll(&self.state, cx)
+    }
+}
+
+impl std::fmt::Debug for Ticker {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("Ticker")
+            .field("ms", &self.ms)
+            .field("started", &self.interval.is_some())
+            .finish()
+    }
+}
+
+
+/// Fires once when the browser is idle, the callback is cancelled when this is dropped.
+///
+/// If `requestIdleCallback` isn't supported then it uses a short timeout instead.
+///
+/// The callback isn't requested until it is polled for the first time.
+pub(crate) struct Idle {
+    state: Rc<RefCell<TimerState>>,
+    callback: Option<IdleCallback>,
+}
+
+impl Idle {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        Self {
+            state: TimerState::new(),
+            callback: None,
+        }
+    }
+
+    pub(crate) fn poll_idle(&mut self, cx: &mut Context) -> Poll<()> {
+        if self.callback.is_none() {
+            let state = self.state.clone();
+
+            self.callback = Some(IdleCallback::new(move || {
+                TimerState::fire(&state);
+            }));
+        }
+
+        TimerState::poll(&self.state, cx)
+    }
+}
+
+impl std::fmt::Debug for Idle {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fmt.debug_struct("Idle")
+            .field("started", &self.callback.is_some())
+            .finish()
+    }
+}
@@ -3,6 +3,7 @@ use crate::dom::RefFn;
 use crate::EventOptions;
 
 pub use crate::animation::AnimatedSignalVec;
+pub use crate::signal::SignalExtra;
 
 
 pub trait StaticEvent {